@@ -0,0 +1,102 @@
+// Servidor de eco WebSocket local, sem dependências, para o demo de tempo real
+// e para os testes do `WsClient`:
+//
+//     node scripts/ws-echo-server.mjs          # ws://127.0.0.1:8765
+//     PORT=9000 node scripts/ws-echo-server.mjs
+//
+// Devolve cada mensagem de texto como veio. `{"type":"close"}` derruba a conexão
+// (para testar a reconexão).
+import { createHash } from "node:crypto";
+import { createServer } from "node:http";
+
+const PORT = Number(process.env.PORT ?? 8765);
+const GUID = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+function frame(opcode, payload) {
+  const length = payload.length;
+  let header;
+  if (length < 126) {
+    header = Buffer.from([0x80 | opcode, length]);
+  } else if (length < 65536) {
+    header = Buffer.alloc(4);
+    header[0] = 0x80 | opcode;
+    header[1] = 126;
+    header.writeUInt16BE(length, 2);
+  } else {
+    header = Buffer.alloc(10);
+    header[0] = 0x80 | opcode;
+    header[1] = 127;
+    header.writeBigUInt64BE(BigInt(length), 2);
+  }
+  return Buffer.concat([header, payload]);
+}
+
+// Quadros do cliente sempre vêm mascarados; devolve `null` se o buffer ainda não
+// tem o quadro inteiro.
+function parse(buffer) {
+  if (buffer.length < 2) return null;
+  const opcode = buffer[0] & 0x0f;
+  let length = buffer[1] & 0x7f;
+  let offset = 2;
+  if (length === 126) {
+    if (buffer.length < 4) return null;
+    length = buffer.readUInt16BE(2);
+    offset = 4;
+  } else if (length === 127) {
+    if (buffer.length < 10) return null;
+    length = Number(buffer.readBigUInt64BE(2));
+    offset = 10;
+  }
+  if (buffer.length < offset + 4 + length) return null;
+  const mask = buffer.subarray(offset, offset + 4);
+  const payload = Buffer.alloc(length);
+  for (let i = 0; i < length; i++) {
+    payload[i] = buffer[offset + 4 + i] ^ mask[i % 4];
+  }
+  return { opcode, payload, size: offset + 4 + length };
+}
+
+const server = createServer((_, res) => {
+  res.writeHead(426).end("WebSocket apenas");
+});
+
+server.on("upgrade", (req, socket) => {
+  const key = req.headers["sec-websocket-key"];
+  if (!key) return socket.destroy();
+  const accept = createHash("sha1").update(key + GUID).digest("base64");
+  socket.write(
+    "HTTP/1.1 101 Switching Protocols\r\n" +
+      "Upgrade: websocket\r\n" +
+      "Connection: Upgrade\r\n" +
+      `Sec-WebSocket-Accept: ${accept}\r\n\r\n`,
+  );
+
+  let pending = Buffer.alloc(0);
+  socket.on("data", (chunk) => {
+    pending = Buffer.concat([pending, chunk]);
+    let message;
+    while ((message = parse(pending))) {
+      pending = pending.subarray(message.size);
+      switch (message.opcode) {
+        case 0x1:
+          if (message.payload.toString() === '{"type":"close"}') {
+            socket.end(frame(0x8, Buffer.from([0x03, 0xe8])));
+            return;
+          }
+          socket.write(frame(0x1, message.payload));
+          break;
+        case 0x8:
+          socket.end(frame(0x8, message.payload));
+          return;
+        case 0x9:
+          socket.write(frame(0xa, message.payload));
+          break;
+      }
+    }
+  });
+  socket.on("error", () => socket.destroy());
+});
+
+server.listen(PORT, "127.0.0.1", () => {
+  console.log(`Eco WebSocket em ws://127.0.0.1:${PORT}`);
+});
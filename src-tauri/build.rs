@@ -1,3 +1,11 @@
 fn main() {
-    tauri_build::build()
+    // Declarar os comandos gera as permissões `allow-<comando>` usadas nos capabilities
+    let manifest = tauri_build::AppManifest::new().commands(&[
+        "greet",
+        "load_settings",
+        "save_settings",
+        "proxy_request",
+    ]);
+    tauri_build::try_build(tauri_build::Attributes::new().app_manifest(manifest))
+        .expect("failed to run tauri-build");
 }
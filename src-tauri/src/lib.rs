@@ -1,14 +1,292 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use tauri::ipc::{CommandScope, GlobalScope, InvokeBody, Request, Response};
+use tauri::{AppHandle, Manager};
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+// --- SETTINGS ---
+
+// A chave vira nome de arquivo; recusa qualquer coisa que possa sair do diretório.
+fn settings_path(app: &AppHandle, key: &str) -> Result<PathBuf, String> {
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("Chave de configuração inválida: {}", key));
+    }
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(format!("{}.json", key)))
+}
+
+#[tauri::command]
+fn load_settings(app: AppHandle, key: String) -> Result<Option<String>, String> {
+    let path = settings_path(&app, &key)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    fs::read_to_string(&path).map(Some).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn save_settings(app: AppHandle, key: String, contents: String) -> Result<(), String> {
+    let path = settings_path(&app, &key)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    // Grava num temporário e renomeia para não deixar o arquivo pela metade
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, contents).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &path).map_err(|e| e.to_string())
+}
+
+// --- PROXY HTTP ---
+
+// Entrada do allow-list em `capabilities/default.json`:
+// `{ "host": "api.exemplo.com" }`, `{ "host": "*.exemplo.com" }` ou `{ "host": "*" }`.
+#[derive(Debug, Deserialize)]
+struct ProxyHost {
+    host: String,
+}
+
+impl ProxyHost {
+    // `host` já chega em minúsculas; o padrão do capability pode vir de qualquer jeito.
+    fn matches(&self, host: &str) -> bool {
+        let pattern = self.host.to_ascii_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(domain) => host
+                .strip_suffix(domain)
+                .is_some_and(|sub| sub.ends_with('.')),
+            None => pattern == "*" || pattern == host,
+        }
+    }
+}
+
+// Requisição e resposta vão como bytes pelo IPC, no mesmo formato: o tamanho de um
+// cabeçalho JSON (u32 big-endian), o JSON e depois o body cru.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProxyRequest {
+    method: String,
+    url: String,
+    headers: HashMap<String, String>,
+    body: ProxyBody,
+    timeout_ms: Option<u64>,
+}
+
+// Num multipart, o body cru é a concatenação dos campos, na ordem de `parts`.
+#[derive(Deserialize)]
+#[serde(tag = "kind", content = "parts")]
+enum ProxyBody {
+    Empty,
+    Raw,
+    Multipart(Vec<ProxyPart>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProxyPart {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    len: usize,
+}
+
+// Status e headers do `HttpResponse` do frontend; o body segue cru
+#[derive(Serialize)]
+struct ProxyResponse {
+    status: u16,
+    headers: HashMap<String, String>,
+}
+
+// Chega no JS como `{ "kind": "Timeout" }` / `{ "kind": "Network", "message": "..." }`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message")]
+enum ProxyError {
+    Forbidden(String),
+    Invalid(String),
+    Timeout,
+    Network(String),
+}
+
+impl From<reqwest::Error> for ProxyError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            ProxyError::Timeout
+        } else {
+            ProxyError::Network(err.to_string())
+        }
+    }
+}
+
+const MAX_REDIRECTS: usize = 10;
+
+fn frame(head: &[u8], body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + head.len() + body.len());
+    out.extend_from_slice(&(head.len() as u32).to_be_bytes());
+    out.extend_from_slice(head);
+    out.extend_from_slice(body);
+    out
+}
+
+fn unframe(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let len = u32::from_be_bytes(bytes.get(..4)?.try_into().ok()?) as usize;
+    let rest = &bytes[4..];
+    (rest.len() >= len).then(|| rest.split_at(len))
+}
+
+// O `multipart::Form` não é `Clone`: é remontado a cada salto de redirecionamento.
+fn multipart_form(parts: &[ProxyPart], mut data: &[u8]) -> Result<reqwest::multipart::Form, ProxyError> {
+    let mut form = reqwest::multipart::Form::new();
+    for part in parts {
+        if part.len > data.len() {
+            return Err(ProxyError::Invalid(format!("Campo {} do multipart truncado", part.name)));
+        }
+        let (bytes, rest) = data.split_at(part.len);
+        data = rest;
+        let mut field = reqwest::multipart::Part::bytes(bytes.to_vec());
+        if let Some(filename) = &part.filename {
+            field = field.file_name(filename.clone());
+        }
+        if let Some(content_type) = &part.content_type {
+            field = field.mime_str(content_type).map_err(|e| ProxyError::Invalid(e.to_string()))?;
+        }
+        form = form.part(part.name.clone(), field);
+    }
+    Ok(form)
+}
+
+// Redirecionamentos são seguidos à mão em `proxy_request`, para que cada salto
+// passe de novo pelo allow-list.
+fn http_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("falha ao criar o cliente HTTP do proxy")
+    })
+}
+
+fn check_url(url: &reqwest::Url, command_scope: &CommandScope<ProxyHost>, global_scope: &GlobalScope<ProxyHost>) -> Result<(), ProxyError> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(ProxyError::Invalid(format!("Esquema não suportado: {}", url.scheme())));
+    }
+    let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+
+    let denied = command_scope.denies().iter().chain(global_scope.denies()).any(|h| h.matches(&host));
+    let allowed = command_scope.allows().iter().chain(global_scope.allows()).any(|h| h.matches(&host));
+    if denied || !allowed {
+        return Err(ProxyError::Forbidden(format!("Host não liberado para o proxy: {}", host)));
+    }
+    Ok(())
+}
+
+// Faz a requisição pelo Rust, fora das regras de CORS do webview. Só para os hosts
+// liberados no capability, inclusive os de destino de um redirecionamento; `deny`
+// vale mais que `allow`.
+#[tauri::command]
+async fn proxy_request(
+    request: Request<'_>,
+    command_scope: CommandScope<ProxyHost>,
+    global_scope: GlobalScope<ProxyHost>,
+) -> Result<Response, ProxyError> {
+    let InvokeBody::Raw(payload) = request.body() else {
+        return Err(ProxyError::Invalid("Esperado body binário".to_string()));
+    };
+    let (head, data) = unframe(payload).ok_or_else(|| ProxyError::Invalid("Requisição truncada".to_string()))?;
+    let request: ProxyRequest = serde_json::from_slice(head).map_err(|e| ProxyError::Invalid(e.to_string()))?;
+
+    let mut url = reqwest::Url::parse(&request.url).map_err(|e| ProxyError::Invalid(e.to_string()))?;
+    let mut method = reqwest::Method::from_bytes(request.method.as_bytes())
+        .map_err(|e| ProxyError::Invalid(e.to_string()))?;
+    let mut headers = request.headers;
+    let mut body = request.body;
+    let mut redirects = 0;
+
+    // O `Content-Type` de um multipart (com o boundary) é do reqwest
+    if matches!(body, ProxyBody::Multipart(_)) {
+        headers.retain(|key, _| !key.eq_ignore_ascii_case("content-type"));
+    }
+
+    let response = loop {
+        check_url(&url, &command_scope, &global_scope)?;
+        let mut builder = http_client().request(method.clone(), url.clone());
+        for (key, value) in &headers {
+            builder = builder.header(key, value);
+        }
+        builder = match &body {
+            ProxyBody::Empty => builder,
+            ProxyBody::Raw => builder.body(data.to_vec()),
+            ProxyBody::Multipart(parts) => builder.multipart(multipart_form(parts, data)?),
+        };
+        if let Some(ms) = request.timeout_ms {
+            builder = builder.timeout(Duration::from_millis(ms));
+        }
+
+        let response = builder.send().await?;
+        let location = response.headers().get(reqwest::header::LOCATION);
+        let Some(location) = location.filter(|_| response.status().is_redirection()) else {
+            break response;
+        };
+        if redirects == MAX_REDIRECTS {
+            return Err(ProxyError::Network(format!("Mais de {} redirecionamentos", MAX_REDIRECTS)));
+        }
+        redirects += 1;
+        let next = location
+            .to_str()
+            .ok()
+            .and_then(|location| url.join(location).ok())
+            .ok_or_else(|| ProxyError::Invalid("Location inválido no redirecionamento".to_string()))?;
+
+        // Como no fetch: 303 (e 301/302 vindos de POST) seguem como GET sem body
+        let status = response.status().as_u16();
+        if (status == 303 && method != reqwest::Method::HEAD) || (matches!(status, 301 | 302) && method == reqwest::Method::POST) {
+            method = reqwest::Method::GET;
+            body = ProxyBody::Empty;
+            headers.retain(|key, _| !key.eq_ignore_ascii_case("content-type"));
+        }
+        // Credenciais não vão para outro host
+        if next.host_str() != url.host_str() {
+            headers.retain(|key, _| !key.eq_ignore_ascii_case("authorization") && !key.eq_ignore_ascii_case("cookie"));
+        }
+        url = next;
+    };
+
+    let status = response.status().as_u16();
+    // Nomes já vêm em minúsculas; valores repetidos são unidos como no fetch
+    let mut headers: HashMap<String, String> = HashMap::new();
+    for (name, value) in response.headers() {
+        let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+        headers
+            .entry(name.as_str().to_string())
+            .and_modify(|existing| {
+                existing.push_str(", ");
+                existing.push_str(&value);
+            })
+            .or_insert(value);
+    }
+    let body = response.bytes().await?;
+
+    let head = serde_json::to_vec(&ProxyResponse { status, headers }).map_err(|e| ProxyError::Network(e.to_string()))?;
+    Ok(Response::new(frame(&head, &body)))
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![greet])
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            load_settings,
+            save_settings,
+            proxy_request
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
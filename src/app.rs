@@ -1,28 +1,77 @@
 mod ui;
+mod graphql;
 mod http;
+mod query;
+mod router;
+mod settings;
+mod ws;
 
 use ui::*;
+use router::{create_router, RouteMode};
 use serde::{Deserialize, Serialize};
 use sycamore::futures::spawn_local_scoped;
 use sycamore::prelude::*;
 // use sycamore::web::events::SubmitEvent;
-use wasm_bindgen::prelude::*;
 use web_sys::{MouseEvent, console};
 
-use crate::app::http::{HttpClient, HttpMethod, HttpRequest, HttpResult};
-
+use crate::app::http::{create_offline_queue, AbortHandle, HttpCache, HttpClient, HttpError, HttpMethod, HttpRequest, HttpResult, Logger, RequestId, RetryPolicy, TauriProxyTransport};
+use crate::form::{self, Field, Form};
+use query::{create_query_client, use_query_with, QueryOptions};
+use sycamore::web::Suspense;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use settings::SettingsStore;
+use ws::{WsClient, WsOptions, WsState};
 
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
-    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
-}
 
 #[derive(Serialize, Deserialize)]
 struct User {
     name: String
 }
 
+// "Última sincronização: há 3 min • 2 pendentes"
+fn sync_label(last_sync: Option<f64>, now: f64, pending: usize) -> String {
+    let quando = match last_sync {
+        None => "nunca".to_string(),
+        Some(at) => match ((now - at).max(0.0) / 60_000.0) as u64 {
+            0 => "agora".to_string(),
+            1 => "há 1 min".to_string(),
+            min if min < 60 => format!("há {} min", min),
+            min => format!("há {} h", min / 60),
+        },
+    };
+    match pending {
+        0 => format!("Última sincronização: {}", quando),
+        1 => format!("Última sincronização: {} • 1 pendente", quando),
+        n => format!("Última sincronização: {} • {} pendentes", quando, n),
+    }
+}
+
+// Evento `message` do job de reindexação
+#[derive(Deserialize)]
+struct JobProgresso {
+    concluidos: u64,
+    total: u64,
+}
+
+impl JobProgresso {
+    fn fraction(&self) -> f64 {
+        if self.total == 0 { 0.0 } else { (self.concluidos as f64 / self.total as f64).min(1.0) }
+    }
+}
+
+// Canal em tempo real, só quando definido na compilação. Para o eco local:
+// `node scripts/ws-echo-server.mjs` e `REALTIME_URL=ws://127.0.0.1:8765`.
+const REALTIME_URL: Option<&str> = option_env!("REALTIME_URL");
+
+// Mensagem do canal em tempo real do dashboard
+#[derive(Serialize, Deserialize, Debug)]
+struct Evento {
+    tipo: String,
+    mensagem: String,
+}
+
+#[derive(Clone, Copy)]
 struct State {
     nome: Signal<String>,
     email: Signal<String>,
@@ -36,80 +85,339 @@ struct State {
     bio: Signal<String>,
 }
 
+// Parte do `State` que sobrevive entre execuções.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+struct Preferences {
+    nome: String,
+    email: String,
+    dark_mode: bool,
+    notifications: bool,
+    volume: f64,
+    brightness: f64,
+    theme_option: usize,
+    bio: String,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            nome: String::from("Maria Oliveira"),
+            email: String::from("maria@example.com"),
+            dark_mode: false,
+            notifications: true,
+            volume: 75.0,
+            brightness: 0.6,
+            theme_option: 0,
+            bio: String::from("Desenvolvedora apaixonada por Rust e UI design."),
+        }
+    }
+}
+
+// v1 guardava o nome em `name` e o volume como inteiro de 0 a 10.
+fn preferences_store() -> SettingsStore<Preferences> {
+    SettingsStore::new("preferences", 2).migration(1, |mut data| {
+        if let Some(obj) = data.as_object_mut() {
+            if let Some(name) = obj.remove("name") {
+                obj.insert("nome".to_string(), name);
+            }
+            if let Some(volume) = obj.get("volume").and_then(|v| v.as_f64()) {
+                obj.insert("volume".to_string(), (volume * 10.0).into());
+            }
+        }
+        data
+    })
+}
+
+impl State {
+    fn new(prefs: Preferences) -> Self {
+        Self {
+            nome: create_signal(prefs.nome),
+            email: create_signal(prefs.email),
+            dark_mode: create_signal(prefs.dark_mode),
+            notifications: create_signal(prefs.notifications),
+            volume: create_signal(prefs.volume),
+            brightness: create_signal(prefs.brightness),
+            loading: create_signal(false),
+            theme_option: create_signal(prefs.theme_option),
+            accept_terms: create_signal(false),
+            bio: create_signal(prefs.bio),
+        }
+    }
+
+    fn preferences(&self) -> Preferences {
+        Preferences {
+            nome: self.nome.get_clone_untracked(),
+            email: self.email.get_clone_untracked(),
+            dark_mode: self.dark_mode.get_untracked(),
+            notifications: self.notifications.get_untracked(),
+            volume: self.volume.get_untracked(),
+            brightness: self.brightness.get_untracked(),
+            theme_option: self.theme_option.get_untracked(),
+            bio: self.bio.get_clone_untracked(),
+        }
+    }
+
+    fn hydrate(&self, prefs: Preferences) {
+        self.nome.set(prefs.nome);
+        self.email.set(prefs.email);
+        self.dark_mode.set(prefs.dark_mode);
+        self.notifications.set(prefs.notifications);
+        self.volume.set(prefs.volume);
+        self.brightness.set(prefs.brightness);
+        self.theme_option.set(prefs.theme_option);
+        self.bio.set(prefs.bio);
+    }
+}
+
 
 #[component]
 pub fn App() -> View {
     // --- ESTADO ---
 
-    let state = State {
-        nome: create_signal(String::from("Maria Oliveira")),
-        email: create_signal(String::from("maria@example.com")),
-        dark_mode: create_signal(false),
-        notifications: create_signal(true),
-        volume: create_signal(75.0),
-        brightness: create_signal(0.6),
-        loading: create_signal(false),
-        theme_option: create_signal(0),
-        accept_terms: create_signal(false),
-        bio: create_signal(
-            String::from("Desenvolvedora apaixonada por Rust e UI design.")
-        )
-    };
+    let state = State::new(Preferences::default());
+
+    let toasts = create_toasts(5);
+    let dialogs = create_dialogs();
+    let theme = create_theme(ThemeMode::Light);
+    let queries = create_query_client();
+    // Respostas GET com `max-age`/`ETag`, compartilhadas pelos componentes
+    provide_context(HttpCache::new(100).persistent());
+
+    // --- TEMPO REAL ---
+    // Servidor de eco: devolve o que enviamos, inclusive o ping
+    let realtime_state = REALTIME_URL.map(|url| {
+        let realtime = WsClient::<Evento, Evento>::connect(url, WsOptions::default());
+        realtime.on_message(|evento| console::log_1(&format!("Tempo real: {:?}", evento).into()));
+        let _ = realtime.send(&Evento { tipo: "hello".to_string(), mensagem: "Dashboard conectado".to_string() });
+        realtime.state()
+    });
+
+    // --- TEMA ---
+    // "Claro/Escuro/Automático" define o modo; o toggle escolhe claro/escuro
+    // explicitamente e reflete o tema em uso (inclusive no automático).
+    create_effect(move || {
+        theme.set_mode(match state.theme_option.get() {
+            1 => ThemeMode::Dark,
+            2 => ThemeMode::Auto,
+            _ => ThemeMode::Light,
+        });
+    });
+    create_effect(move || {
+        let dark = theme.is_dark();
+        if state.dark_mode.get_untracked() != dark {
+            state.dark_mode.set(dark);
+        }
+    });
+    create_effect(move || {
+        let dark = state.dark_mode.get();
+        if dark != untrack(|| theme.is_dark()) {
+            state.theme_option.set(if dark { 1 } else { 0 });
+        }
+    });
+
+    // --- VALIDAÇÃO DO PERFIL ---
+    let perfil = Form::new();
+    let nome_field = perfil.register(
+        Field::from_signal(state.nome).validate(form::required("Informe seu nome"))
+    );
+    let email_field = perfil.register(
+        Field::from_signal(state.email)
+            .validate(form::required("Informe seu e-mail"))
+            .validate(form::email("E-mail inválido"))
+            .validate_async(form::custom_async(|email: String| async move {
+                let client = HttpClient::new("https://httpbin.org");
+                let query = serde_json::json!({ "email": email });
+                match client.get("/anything/users/available").query(&query).send().await {
+                    Err(HttpError::Status { status: 409, .. }) => Err("E-mail já cadastrado".to_string()),
+                    // Sem rede não bloqueia o formulário; o servidor valida de novo ao salvar
+                    _ => Ok(()),
+                }
+            }))
+    );
+
+    // --- PERSISTÊNCIA ---
+    let store = preferences_store();
+    spawn_local_scoped({
+        let store = store.clone();
+        async move {
+            state.loading.set(true);
+            match store.load().await {
+                Ok(prefs) => {
+                    state.hydrate(prefs);
+                    perfil.commit();
+                }
+                Err(e) => {
+                    console::log_1(&format!("❌ {}", e).into());
+                    toasts.warning(format!("Usando configurações padrão. {}", e));
+                }
+            }
+            state.loading.set(false);
+        }
+    });
+
+    // --- SINCRONIZAÇÃO ---
+    // Alterações enviadas sem rede ficam na fila e saem quando a conexão voltar
+    let offline = create_offline_queue();
+    let sync_client = HttpClient::new("https://httpbin.org")
+        .with_timeout(10_000)
+        .with_middleware(offline);
+    offline.replay_through(&sync_client);
+
+    // Relógio do "há N min" no StatusBar
+    let agora = create_signal(js_sys::Date::now());
+    let tick = Closure::<dyn Fn()>::new(move || agora.set(js_sys::Date::now()));
+    let relogio = window()
+        .set_interval_with_callback_and_timeout_and_arguments_0(tick.as_ref().unchecked_ref(), 30_000)
+        .ok();
+    on_cleanup(move || {
+        if let Some(handle) = relogio {
+            window().clear_interval_with_handle(handle);
+        }
+        drop(tick);
+    });
 
     let save_action = move |_| {
-        state.loading.set(true);
-        console::log_1(&format!("Salvando configurações para: {}", state.nome.get_clone()).into());
-        // Simular um delay
-        state.loading.set(false);
+        let store = store.clone();
+        let sync_client = sync_client.clone();
+        spawn_local_scoped(async move {
+            let valid = perfil.submit(|| async move {
+                state.loading.set(true);
+                console::log_1(&format!("Salvando configurações para: {}", state.nome.get_clone()).into());
+                let prefs = state.preferences();
+                match store.save(&prefs).await {
+                    Ok(()) => match sync_client.put("/put").json(&prefs).send().await {
+                        Ok(_) => toasts.success("Configurações salvas."),
+                        Err(HttpError::Queued) => toasts.info("Configurações salvas. Sem conexão: serão sincronizadas ao reconectar."),
+                        Err(e) => toasts.warning(format!("Configurações salvas só neste dispositivo. {}", e)),
+                    },
+                    Err(e) => toasts.push(
+                        Toast::new(BadgeKind::Danger, e.to_string())
+                            .title("Erro ao salvar")
+                            .persistent()
+                    ),
+                };
+                state.loading.set(false);
+            }).await;
+            if !valid {
+                toasts.warning("Corrija os campos destacados antes de salvar.");
+            }
+        });
     };
 
 
-    let export_action = move |_e: MouseEvent| {
+    let export_progress = create_signal(0.0);
+    let exporting = create_signal(false);
+
+    // Recebe o handle da aba: trocar de aba cancela a exportação em andamento.
+    let export_action = move |abort: AbortHandle| {
 
-        async fn make_request() -> HttpResult<()>  {
+        async fn make_request(progress: Signal<f64>, abort: AbortHandle) -> HttpResult<()>  {
             console::log_1(&"Exportando configurações...".into());
 
-            // 1. Requisição simples
-            let resp: http::HttpResponse = http::get("https://httpbin.org/get").await?;
+            // 1. Requisição simples, com prazo e novas tentativas em falhas transitórias
+            let resp: http::HttpResponse = HttpRequest::new(HttpMethod::GET, "https://httpbin.org/get")
+                .timeout(5_000)
+                .retry(RetryPolicy::default())
+                .abort_with(&abort)
+                .send()
+                .await?;
             // let users: Vec<User> = resp.json()?;
 
             // 2. POST com JSON
             let new_user = User { name: "João".to_string() };
             let resp: http::HttpResponse = HttpRequest::new(HttpMethod::POST, "https://httpbin.org/post")
                 .json(&new_user)
+                .abort_with(&abort)
                 .send()
                 .await?;
 
             // 3. Com autenticação
             let resp: http::HttpResponse = HttpRequest::new(HttpMethod::GET, "https://httpbin.org/get")
                 .bearer("seu-token-aqui")
+                .abort_with(&abort)
                 .send()
                 .await?;
 
             // 4. Cliente reutilizável
             let client: HttpClient = HttpClient::new("https://httpbin.org/")
                 .with_bearer("token-123")
-                .with_header("X-Custom", "value");
+                .with_header("X-Custom", "value")
+                .with_timeout(10_000)
+                .with_retry(RetryPolicy::default().attempts(4))
+                .with_middleware(RequestId::default())
+                .with_middleware(Logger)
+                .with_abort(&abort)
+                // No desktop passa pelo backend (sem CORS); no navegador usa o fetch
+                .with_transport(TauriProxyTransport);
 
             let users: http::HttpResponse = client.get("/get").send().await?;
             let created: http::HttpResponse = client.post("/post").json(&new_user).send().await?;
 
+            // 5. Download binário com progresso
+            let archive: http::HttpResponse = client.get("/stream-bytes/262144?chunk_size=8192")
+                .on_progress(move |p| progress.set(p.fraction().unwrap_or(0.0)))
+                .send()
+                .await?;
+            let blob = archive.blob()?;
+            console::log_1(&format!("📦 {} bytes ({:?})", blob.size(), archive.content_type()).into());
+
             Ok(())
 
         }
 
         spawn_local_scoped(async move {
-            match make_request().await {
+            export_progress.set(0.0);
+            exporting.set(true);
+            let result = make_request(export_progress, abort).await;
+            exporting.set(false);
+            match result {
                 Ok(_) => {
                     console::log_1(&"✅ Exportação concluída com sucesso!".into());
+                    toasts.success("Exportação concluída com sucesso!");
+                }
+                Err(HttpError::Aborted) => {
+                    console::log_1(&"Exportação cancelada.".into());
+                }
+                Err(e) if e.is_offline() => {
+                    toasts.warning("Sem conexão. Tente exportar novamente quando voltar a ficar online.");
+                }
+                Err(HttpError::Status { status: 401, .. }) => {
+                    toasts.push(
+                        Toast::new(BadgeKind::Warning, "Sua sessão expirou. Entre novamente para exportar.")
+                            .title("Não autorizado")
+                    );
                 }
                 Err(e) => {
                     console::error_1(&format!("❌ Erro na exportação: {}", e).into());
+                    toasts.push(
+                        Toast::new(BadgeKind::Danger, e.to_string())
+                            .title("Erro na exportação")
+                            .persistent()
+                    );
                 }
             }
         });
     };
 
+    // --- ROTAS ---
+    // Deep links no formato `#/settings/<aba>` selecionam a aba correspondente.
+    let tab_slugs = ["profile", "appearance", "system", "advanced"];
+    let active_tab = create_signal(0);
+    let router = create_router(RouteMode::Hash);
+
+    create_effect(move || {
+        let slug = router.params("/settings/:tab").and_then(|p| p.get("tab").map(str::to_string));
+        let idx = slug.and_then(|s| tab_slugs.iter().position(|t| *t == s)).unwrap_or(0);
+        if idx != active_tab.get_untracked() {
+            active_tab.set(idx);
+        }
+    });
+
+    create_effect(move || {
+        router.replace(&format!("/settings/{}", tab_slugs[active_tab.get()]));
+    });
+
     // --- DEFINIÇÃO DAS ABAS ---
     let tabs_config = vec![
 
@@ -142,7 +450,9 @@ pub fn App() -> View {
                                 TextField(
                                     value = state.nome,
                                     placeholder = "Digite seu nome",
-                                    grow = true
+                                    grow = true,
+                                    error = nome_field.error,
+                                    touched = nome_field.touched
                                 )
                             }
                             Column(spacing = 8) {
@@ -153,7 +463,9 @@ pub fn App() -> View {
                                 TextField(
                                     value = state.email,
                                     placeholder = "seu@email.com",
-                                    grow = true
+                                    grow = true,
+                                    error = email_field.error,
+                                    touched = email_field.touched
                                 )
                             }
                         }
@@ -319,6 +631,12 @@ pub fn App() -> View {
 
             Column(spacing = 20) {
                 Label(text = "Configurações do Sistema".to_string(), kind = LabelKind::Header)
+
+                Card() {
+                    Suspense(fallback = || view! { Spinner(size = 20) }) {
+                        ConexaoInfo()
+                    }
+                }
                 
                 Card() {
                     Column(spacing = 20) {
@@ -446,201 +764,330 @@ pub fn App() -> View {
             }
         }),
 
-        TabItem::new("Avançado", move || view! {
-            Column(spacing = 20) {
-                Row(spacing = 12, align = Align::Between) {
-                    Label(
-                        text = "Configurações Avançadas".to_string(),
-                        kind = LabelKind::Header
-                    )
-                    Badge(
-                        text = "Cuidado".to_owned(),
-                        kind = BadgeKind::Warning
-                    )
+        TabItem::new("Avançado", move || {
+            // Requisições da aba; o TabView descarta o escopo ao trocar de aba
+            let tab_requests = AbortHandle::scoped();
+            let http_cache = use_context::<HttpCache>();
+
+            // Progresso da reindexação rodando no servidor, via SSE
+            let reindexacao = http::subscribe::<JobProgresso>("/api/jobs/reindex/events");
+            let reindex_progress = create_signal(0.0);
+            create_effect(move || {
+                if let Some(job) = reindexacao.with(|job| job.as_ref().map(JobProgresso::fraction)) {
+                    reindex_progress.set(job);
                 }
+            });
 
-                Card() {
-                    Column(spacing = 16) {
+            view! {
+                Column(spacing = 20) {
+                    Row(spacing = 12, align = Align::Between) {
                         Label(
-                            text = "Ferramentas de Desenvolvedor".to_string(),
-                            kind = LabelKind::Body
+                            text = "Configurações Avançadas".to_string(),
+                            kind = LabelKind::Header
                         )
-                        
-                        Row(spacing = 8) {
-                            Button(
-                                text = "Exportar Logs",
-                                kind = ButtonKind::Secondary,
-                                on_click = Box::new(export_action)
-                            )
-                            Button(
-                                text = "Limpar Cache",
-                                kind = ButtonKind::Ghost,
-                                on_click = Box::new(
-                                    move |_| console::log_1(&"Limpando cache...".into())
-                                )
-                            )
-                        }
-
-                        Separator()
+                        Badge(
+                            text = "Cuidado".to_owned(),
+                            kind = BadgeKind::Warning
+                        )
+                    }
 
-                        Column(spacing = 12) {
+                    Card() {
+                        Column(spacing = 16) {
                             Label(
-                                text = "Zona de Perigo".to_string(),
+                                text = "Ferramentas de Desenvolvedor".to_string(),
                                 kind = LabelKind::Body
                             )
-                            Label(
-                                text = 
-                                    "Ações irreversíveis que afetam permanentemente sua conta"
-                                    .to_string(),
-                                kind = LabelKind::Caption
-                            )
-                            Button(
-                                text = "Resetar Configurações",
-                                kind = ButtonKind::Destructive,
-                                on_click = Box::new(
-                                    move |_| console::log_1(&"Resetando...".into())
+                        
+                            Row(spacing = 8) {
+                                Button(
+                                    text = "Exportar Logs",
+                                    kind = ButtonKind::Secondary,
+                                    on_click = Box::new(move |_| export_action(tab_requests.clone()))
                                 )
-                            )
-                        }
-                    }
-                }
-                (
-                    if_else(
-                        state.loading, ||
-                        view! {
-                            Card() {
-                                Row(spacing = 12, align = Align::Center) {
-                                    Spinner(size = 20)
+                                Button(
+                                    text = "Limpar Cache",
+                                    kind = ButtonKind::Ghost,
+                                    on_click = Box::new(
+                                        move |_| {
+                                            console::log_1(&"Limpando cache...".into());
+                                            queries.invalidate("");
+                                            http_cache.purge("");
+                                        }
+                                    )
+                                )
+                            }
+
+                            (if exporting.get() {
+                                view! {
+                                    ProgressBar(
+                                        value = export_progress,
+                                        show_label = true,
+                                        indeterminate = false
+                                    )
+                                }
+                            } else {
+                                view! {}
+                            })
+
+                            (if reindexacao.with(Option::is_some) {
+                                view! {
                                     Label(
-                                        text = "Processando...".to_string(),
-                                        kind = LabelKind::Body
+                                        text = "Reindexação no servidor".to_string(),
+                                        kind = LabelKind::Caption
+                                    )
+                                    ProgressBar(
+                                        value = reindex_progress,
+                                        show_label = true,
+                                        indeterminate = false
                                     )
                                 }
+                            } else {
+                                view! {}
+                            })
+
+                            Separator()
+
+                            Column(spacing = 12) {
+                                Label(
+                                    text = "Zona de Perigo".to_string(),
+                                    kind = LabelKind::Body
+                                )
+                                Label(
+                                    text = 
+                                        "Ações irreversíveis que afetam permanentemente sua conta"
+                                        .to_string(),
+                                    kind = LabelKind::Caption
+                                )
+                                Button(
+                                    text = "Resetar Configurações",
+                                    kind = ButtonKind::Destructive,
+                                    on_click = Box::new(move |_| {
+                                        spawn_local_scoped(async move {
+                                            let confirmed = dialogs.confirm_destructive(
+                                                "Todas as configurações voltarão ao padrão. Deseja continuar?"
+                                            ).await;
+                                            if confirmed {
+                                                console::log_1(&"Resetando...".into());
+                                            }
+                                        });
+                                    })
+                                )
                             }
-                        }, ||
-                        view! {}
+                        }
+                    }
+                    (
+                        if_else(
+                            state.loading, ||
+                            view! {
+                                Card() {
+                                    Row(spacing = 12, align = Align::Center) {
+                                        Spinner(size = 20)
+                                        Label(
+                                            text = "Processando...".to_string(),
+                                            kind = LabelKind::Body
+                                        )
+                                    }
+                                }
+                            }, ||
+                            view! {}
+                        )
                     )
-                )
 
+                }
             }
         }),
     ];
 
     // --- VIEW ---
     view! {
-        Window(
-            title = "Preferências do Sistema", size = (900, 650),
-            resizable = false
-        ) {
-            Column(spacing = 0, align = Align::Stretch, class = "h-full") {
-                Toolbar() {
-                    Row(spacing = 8) {
-                        IconButton(
-                            icon_src="/public/icons/edit.svg",
-                            alt_text="Editar",
-                            on_click = Box::new(|_| ()),
-                            tooltip = "Voltar"
-                        )
-                        IconButton(
-                            icon_src="/public/icons/edit.svg",
-                            alt_text="Editar",
-                            on_click = Box::new(|_| ()),
-                            tooltip = "Configurações"
-                        )
-                        Spacer()
-                        IconButton(
-                            icon_src="/public/icons/edit.svg",
-                            alt_text="Editar",
-                            on_click = Box::new(|_| ()),
-                            tooltip = "Configurações",
-                        )
-
-                    }
-                }
-
-                TabView(tabs = tabs_config)
-
-                StatusBar() {
-                    Row(
-                        spacing = 16,
-                        align = Align::Between,
-                        style = "width: 100%;"
+        ThemeProvider {
+            ToastProvider(position = ToastPosition::BottomRight) {
+                DialogProvider {
+                    Window(
+                        title = "Preferências do Sistema", size = (900, 650),
+                        resizable = false
                     ) {
-                        Row(spacing = 8) {
-                            Label(
-                                text = "v1.2.4".to_string(),
-                                kind = LabelKind::Caption
-                            )
-                            Label(
-                                text = "•".to_string(),
-                                kind = LabelKind::Caption
-                            )
-                            Label(
-                                text = "Última sincronização: 2 min atrás".to_string(),
-                                kind = LabelKind::Caption
-                            )
-                        }
+                        Column(spacing = 0, align = Align::Stretch, class = "h-full") {
+                            Toolbar() {
+                                Row(spacing = 8) {
+                                    IconButton(
+                                        icon_src="/public/icons/edit.svg",
+                                        alt_text="Editar",
+                                        on_click = Box::new(|_| ()),
+                                        tooltip = "Voltar"
+                                    )
+                                    IconButton(
+                                        icon_src="/public/icons/edit.svg",
+                                        alt_text="Editar",
+                                        on_click = Box::new(|_| ()),
+                                        tooltip = "Configurações"
+                                    )
+                                    Spacer()
+                                    IconButton(
+                                        icon_src="/public/icons/edit.svg",
+                                        alt_text="Editar",
+                                        on_click = Box::new(|_| ()),
+                                        tooltip = "Configurações",
+                                    )
 
+                                }
+                            }
 
+                            TabView(tabs = tabs_config, active = active_tab)
 
-                        Row(spacing = 12) {
-                            (
-                                if_else(
-                                    state.loading, ||
-                                    view! {
-                                        Row(spacing = 8) {
-                                            Spinner(size = 12)
+                            StatusBar() {
+                                Row(
+                                    spacing = 16,
+                                    align = Align::Between,
+                                    style = "width: 100%;"
+                                ) {
+                                    Row(spacing = 8) {
+                                        Label(
+                                            text = "v1.2.4".to_string(),
+                                            kind = LabelKind::Caption
+                                        )
+                                        Label(
+                                            text = "•".to_string(),
+                                            kind = LabelKind::Caption
+                                        )
+                                        (move || view! {
                                             Label(
-                                                text = "Salvando...".to_string(),
+                                                text = sync_label(offline.last_sync.get(), agora.get(), offline.pending_count.get()),
                                                 kind = LabelKind::Caption
                                             )
-                                        }
-                                    }, ||
-                                    view! {
-                                        Label(
-                                            text = "✓ Pronto".to_string(),
-                                            kind = LabelKind::Caption
+                                        })
+                                        (realtime_state.map(|realtime_state| view! {
+                                            Label(
+                                                text = "•".to_string(),
+                                                kind = LabelKind::Caption
+                                            )
+                                            (move || {
+                                                let texto = match realtime_state.get() {
+                                                    WsState::Connecting => "Tempo real: conectando...".to_string(),
+                                                    WsState::Open => "Tempo real: conectado".to_string(),
+                                                    WsState::Reconnecting { attempt } => format!("Tempo real: reconectando ({}ª tentativa)", attempt),
+                                                    WsState::Closed => "Tempo real: desconectado".to_string(),
+                                                };
+                                                view! { Label(text = texto, kind = LabelKind::Caption) }
+                                            })
+                                        }))
+                                    }
+
+
+
+                                    Row(spacing = 12) {
+                                        (
+                                            if_else(
+                                                state.loading, ||
+                                                view! {
+                                                    Row(spacing = 8) {
+                                                        Spinner(size = 12)
+                                                        Label(
+                                                            text = "Salvando...".to_string(),
+                                                            kind = LabelKind::Caption
+                                                        )
+                                                    }
+                                                }, ||
+                                                view! {
+                                                    Label(
+                                                        text = "✓ Pronto".to_string(),
+                                                        kind = LabelKind::Caption
+                                                    )
+                                                }
+                                            )
                                         )
                                     }
+                                }
+                            }
+
+                            Separator()
+
+                            Row(
+                                spacing = 12,
+                                align = Align::End,
+                                style = "padding: 16px; background: var(--bg-surface);"
+                            ) {
+                                Button(
+                                    text = "Cancelar",
+                                    kind = ButtonKind::Ghost,
+                                    on_click = Box::new(
+                                        move |_| console::log_1(&"Cancelado".into())
+                                    )
                                 )
-                            )
+                                Button(
+                                    text = "Aplicar",
+                                    kind = ButtonKind::Secondary,
+                                    on_click = Box::new(
+                                        move |_| console::log_1(&"Aplicando...".into())
+                                    )
+                                )
+                                Button(
+                                    text = "Salvar Alterações",
+                                    kind = ButtonKind::Primary,
+                                    on_click = Box::new(save_action),
+                                )
+                            }
                         }
                     }
                 }
+            }
+        }
+    }
+}
+
 
-                Separator()
+#[derive(Deserialize)]
+struct Origem {
+    origin: String,
+}
+
+// Endereço público visto pelo servidor. Fica em cache por um minuto, então
+// voltar para a aba não dispara outra requisição.
+#[component]
+fn ConexaoInfo() -> View {
+    let client = HttpClient::new("https://httpbin.org").with_middleware(use_context::<HttpCache>());
+    let origem = use_query_with(
+        "conexao/ip",
+        QueryOptions::default().stale(60_000),
+        move || {
+            let request = client.get("/ip");
+            async move { Ok(request.send().await?.json::<Origem>()?) }
+        }
+    );
+
+    let detalhe = move || {
+        let texto = match (origem.data.get_clone(), origem.error.get_clone()) {
+            (Some(o), _) => o.origin.clone(),
+            (None, Some(e)) => e.to_string(),
+            (None, None) => "—".to_string(),
+        };
+        view! { Label(text = texto, kind = LabelKind::Caption) }
+    };
 
-                Row(
-                    spacing = 12,
-                    align = Align::End,
-                    style = "padding: 16px; background: var(--bg-surface);"
-                ) {
+    view! {
+        Row(spacing = 12, align = Align::Between) {
+            Column(spacing = 4, align = Align::Start) {
+                Label(
+                    text = "Endereço Público".to_string(),
+                    kind = LabelKind::Body
+                )
+                (detalhe)
+            }
+            (if origem.fetching.get() {
+                view! { Spinner(size = 12) }
+            } else {
+                view! {
                     Button(
-                        text = "Cancelar",
+                        text = "Atualizar",
                         kind = ButtonKind::Ghost,
-                        on_click = Box::new(
-                            move |_| console::log_1(&"Cancelado".into())
-                        )
-                    )
-                    Button(
-                        text = "Aplicar",
-                        kind = ButtonKind::Secondary,
-                        on_click = Box::new(
-                            move |_| console::log_1(&"Aplicando...".into())
-                        )
-                    )
-                    Button(
-                        text = "Salvar Alterações",
-                        kind = ButtonKind::Primary,
-                        on_click = Box::new(save_action),
+                        on_click = Box::new(move |_| origem.refetch())
                     )
                 }
-            }
+            })
         }
     }
 }
 
-
 fn if_else<F1, F2>(signal_cond: Signal<bool>, then_view: F1, else_view: F2) -> View
 where
     F1: FnOnce() -> View,
@@ -0,0 +1,355 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use super::http::{HttpClient, HttpError, HttpRequest, HttpResponse};
+
+// --- ERROS ---
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphQlLocation {
+    pub line: u32,
+    pub column: u32,
+}
+
+// Um item do array `errors` da resposta.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphQlErrorEntry {
+    pub message: String,
+    #[serde(default)]
+    pub locations: Vec<GraphQlLocation>,
+    // Campo ou índice de lista até o ponto do erro, ex.: `["user", "posts", 0]`
+    #[serde(default)]
+    pub path: Vec<Value>,
+    #[serde(default)]
+    pub extensions: Option<Value>,
+}
+
+impl GraphQlErrorEntry {
+    // `extensions.code`, quando o servidor informa (ex.: `UNAUTHENTICATED`).
+    pub fn code(&self) -> Option<&str> {
+        self.extensions.as_ref()?.get("code")?.as_str()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum GraphQlError {
+    Http(HttpError),
+    // O servidor respondeu com `errors`; `data` traz o que foi resolvido mesmo assim
+    Graphql {
+        errors: Vec<GraphQlErrorEntry>,
+        data: Option<Value>,
+    },
+    Decode(String),
+}
+
+impl std::fmt::Display for GraphQlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphQlError::Http(err) => write!(f, "{}", err),
+            GraphQlError::Graphql { errors, .. } => {
+                let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+                write!(f, "Erro GraphQL: {}", messages.join("; "))
+            }
+            GraphQlError::Decode(msg) => write!(f, "Resposta GraphQL inválida: {}", msg),
+        }
+    }
+}
+
+impl From<HttpError> for GraphQlError {
+    fn from(err: HttpError) -> Self {
+        GraphQlError::Http(err)
+    }
+}
+
+pub type GraphQlResult<T> = Result<T, GraphQlError>;
+
+// --- OPERAÇÕES ---
+
+#[derive(Debug, Clone)]
+pub struct Operation {
+    query: String,
+    variables: Option<Value>,
+    operation_name: Option<String>,
+    invalid: Option<String>,
+}
+
+impl Operation {
+    pub fn new(query: impl Into<String>) -> Self {
+        Self { query: query.into(), variables: None, operation_name: None, invalid: None }
+    }
+
+    pub fn variables<V: Serialize + ?Sized>(mut self, variables: &V) -> Self {
+        match serde_json::to_value(variables) {
+            Ok(value) => self.variables = Some(value),
+            Err(err) => self.invalid = Some(format!("variables: {}", err)),
+        }
+        self
+    }
+
+    // Necessário quando o documento tem mais de uma operação.
+    pub fn operation_name(mut self, name: impl Into<String>) -> Self {
+        self.operation_name = Some(name.into());
+        self
+    }
+
+    // SHA-256 do documento em hexadecimal, como no protocolo de persisted queries.
+    pub fn hash(&self) -> String {
+        Sha256::digest(self.query.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // Com `persisted`, manda só o hash; `with_query` inclui o documento junto.
+    fn to_json(&self, persisted: bool, with_query: bool) -> Value {
+        let mut body = json!({});
+        if with_query {
+            body["query"] = Value::String(self.query.clone());
+        }
+        if let Some(variables) = &self.variables {
+            body["variables"] = variables.clone();
+        }
+        if let Some(name) = &self.operation_name {
+            body["operationName"] = Value::String(name.clone());
+        }
+        if persisted {
+            body["extensions"] = json!({ "persistedQuery": { "version": 1, "sha256Hash": self.hash() } });
+        }
+        body
+    }
+}
+
+// --- RESPOSTA ---
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GraphQlResponse {
+    #[serde(default)]
+    pub data: Option<Value>,
+    #[serde(default)]
+    pub errors: Vec<GraphQlErrorEntry>,
+    #[serde(default)]
+    pub extensions: Option<Value>,
+}
+
+impl GraphQlResponse {
+    // Qualquer item em `errors` vira `GraphQlError::Graphql`, mesmo com `data` parcial.
+    pub fn into_result<T: DeserializeOwned>(self) -> GraphQlResult<T> {
+        if !self.errors.is_empty() {
+            return Err(GraphQlError::Graphql { errors: self.errors, data: self.data });
+        }
+        let data = self.data.ok_or_else(|| GraphQlError::Decode("resposta sem `data`".to_string()))?;
+        serde_json::from_value(data).map_err(|e| GraphQlError::Decode(e.to_string()))
+    }
+
+    fn is_graphql(&self) -> bool {
+        self.data.is_some() || !self.errors.is_empty()
+    }
+
+    fn persisted_query_missing(&self) -> bool {
+        self.errors.iter().any(|e| {
+            e.message == "PersistedQueryNotFound" || e.code() == Some("PERSISTED_QUERY_NOT_FOUND")
+        })
+    }
+}
+
+// --- CLIENTE ---
+
+// Operações GraphQL sobre um `HttpClient` (headers, timeout, retry, middlewares e
+// transporte vêm dele):
+//
+//     let gql = GraphQlClient::new(HttpClient::new("https://api.local").with_bearer(token), "/graphql");
+//     let user: UserQuery = gql
+//         .query(Operation::new("query($id: ID!) { user(id: $id) { name } }").variables(&json!({ "id": 1 })))
+//         .await?;
+//
+// Com `persisted_queries()` manda primeiro só o hash do documento e reenvia o texto
+// se o servidor ainda não o conhecer (Automatic Persisted Queries).
+#[derive(Clone)]
+pub struct GraphQlClient {
+    http: HttpClient,
+    path: String,
+    persisted: bool,
+}
+
+impl GraphQlClient {
+    pub fn new(http: HttpClient, path: impl Into<String>) -> Self {
+        Self { http, path: path.into(), persisted: false }
+    }
+
+    pub fn persisted_queries(mut self) -> Self {
+        self.persisted = true;
+        self
+    }
+
+    // Queries e mutations vão do mesmo jeito: POST com JSON.
+    pub async fn query<T: DeserializeOwned>(&self, operation: Operation) -> GraphQlResult<T> {
+        self.execute(&operation).await?.into_result()
+    }
+
+    pub async fn mutate<T: DeserializeOwned>(&self, operation: Operation) -> GraphQlResult<T> {
+        self.query(operation).await
+    }
+
+    // Resposta crua, para quem precisa de `data` parcial ou de `extensions`.
+    pub async fn execute(&self, operation: &Operation) -> GraphQlResult<GraphQlResponse> {
+        if let Some(message) = &operation.invalid {
+            return Err(HttpError::InvalidRequest(message.clone()).into());
+        }
+        if self.persisted {
+            let response = self.post(&operation.to_json(true, false)).await?;
+            if !response.persisted_query_missing() {
+                return Ok(response);
+            }
+            return self.post(&operation.to_json(true, true)).await;
+        }
+        self.post(&operation.to_json(false, true)).await
+    }
+
+    // Várias operações numa única requisição; as respostas voltam na mesma ordem.
+    // O servidor precisa aceitar batching (array no body). Com persisted queries o
+    // documento vai junto do hash, já registrando-o para as próximas chamadas.
+    pub async fn batch(&self, operations: &[Operation]) -> GraphQlResult<Vec<GraphQlResponse>> {
+        if let Some(message) = operations.iter().find_map(|op| op.invalid.as_ref()) {
+            return Err(HttpError::InvalidRequest(message.clone()).into());
+        }
+        let body: Vec<Value> = operations.iter().map(|op| op.to_json(self.persisted, true)).collect();
+        let result = self.request().json(&body).send().await;
+        let responses = decode(result, |responses: &Vec<GraphQlResponse>| responses.iter().all(GraphQlResponse::is_graphql))?;
+        if responses.len() != operations.len() {
+            return Err(GraphQlError::Decode(format!(
+                "{} respostas para {} operações",
+                responses.len(),
+                operations.len()
+            )));
+        }
+        Ok(responses)
+    }
+
+    fn request(&self) -> HttpRequest {
+        self.http.post(&self.path).header("Accept", "application/graphql-response+json, application/json")
+    }
+
+    async fn post(&self, body: &Value) -> GraphQlResult<GraphQlResponse> {
+        decode(self.request().json(body).send().await, GraphQlResponse::is_graphql)
+    }
+}
+
+// Servidores GraphQL costumam mandar `errors` junto com 4xx/5xx; se o body for uma
+// resposta GraphQL válida, ela vale mais que o status.
+fn decode<T: DeserializeOwned>(result: Result<HttpResponse, HttpError>, is_graphql: impl Fn(&T) -> bool) -> GraphQlResult<T> {
+    match result {
+        Ok(response) => response.json().map_err(|e| GraphQlError::Decode(e.to_string())),
+        Err(HttpError::Status { status, headers, body }) => match serde_json::from_str::<T>(&body) {
+            Ok(parsed) if is_graphql(&parsed) => Ok(parsed),
+            _ => Err(HttpError::Status { status, headers, body }.into()),
+        },
+        Err(err) => Err(err.into()),
+    }
+}
+
+// Nativos (`cargo test`), sobre o `MockTransport`.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::app::http::{HttpMethod, MockTransport, RequestBody};
+
+    fn client(mock: &MockTransport) -> GraphQlClient {
+        GraphQlClient::new(HttpClient::new("https://api.local").with_transport(mock.clone()), "/graphql")
+    }
+
+    fn sent_json(mock: &MockTransport) -> Value {
+        match mock.last_request().and_then(|r| r.body) {
+            Some(RequestBody::Text(text)) => serde_json::from_str(&text).unwrap(),
+            _ => panic!("requisição sem body JSON"),
+        }
+    }
+
+    fn response(raw: Value) -> GraphQlResponse {
+        serde_json::from_value(raw).unwrap()
+    }
+
+    #[test]
+    fn hash_is_sha256_hex_of_the_document() {
+        let hash = Operation::new("{ __typename }").hash();
+        assert_eq!(hash, "7f56e67dd21ab3f30d1ff8b7bed08893f0a0db86449836189b361dd1e56ddb4b");
+    }
+
+    #[test]
+    fn to_json_includes_only_what_was_set() {
+        let op = Operation::new("{ __typename }");
+        assert_eq!(op.to_json(false, true), json!({ "query": "{ __typename }" }));
+
+        let op = op.variables(&json!({ "id": 1 })).operation_name("Tipo");
+        assert_eq!(
+            op.to_json(false, true),
+            json!({ "query": "{ __typename }", "variables": { "id": 1 }, "operationName": "Tipo" })
+        );
+    }
+
+    #[test]
+    fn to_json_persisted_sends_hash_with_or_without_query() {
+        let op = Operation::new("{ __typename }");
+        let extensions = json!({ "persistedQuery": { "version": 1, "sha256Hash": op.hash() } });
+        assert_eq!(op.to_json(true, false), json!({ "extensions": extensions }));
+        assert_eq!(op.to_json(true, true), json!({ "query": "{ __typename }", "extensions": extensions }));
+    }
+
+    #[test]
+    fn into_result_prefers_errors_over_partial_data() {
+        let ok: Value = response(json!({ "data": { "me": 1 } })).into_result().unwrap();
+        assert_eq!(ok, json!({ "me": 1 }));
+
+        let partial = response(json!({ "data": { "me": null }, "errors": [{ "message": "negado" }] }));
+        let err = partial.into_result::<Value>().unwrap_err();
+        assert!(matches!(&err, GraphQlError::Graphql { errors, data: Some(_) } if errors[0].message == "negado"));
+
+        let empty = GraphQlResponse::default().into_result::<Value>().unwrap_err();
+        assert!(matches!(empty, GraphQlError::Decode(_)));
+    }
+
+    #[test]
+    fn persisted_query_missing_by_message_or_code() {
+        assert!(response(json!({ "errors": [{ "message": "PersistedQueryNotFound" }] })).persisted_query_missing());
+        let by_code = json!({ "errors": [{ "message": "x", "extensions": { "code": "PERSISTED_QUERY_NOT_FOUND" } }] });
+        assert!(response(by_code).persisted_query_missing());
+        assert!(!response(json!({ "errors": [{ "message": "outro" }] })).persisted_query_missing());
+    }
+
+    #[test]
+    fn graphql_body_on_4xx_wins_over_status() {
+        let mock = MockTransport::new()
+            .on(HttpMethod::POST, "/graphql", 400, r#"{"errors":[{"message":"campo inválido"}]}"#).once()
+            .on(HttpMethod::POST, "/graphql", 400, "Bad Request");
+        let gql = client(&mock);
+
+        let err = block_on(gql.query::<Value>(Operation::new("{ x }"))).unwrap_err();
+        assert!(matches!(&err, GraphQlError::Graphql { errors, .. } if errors[0].message == "campo inválido"));
+
+        let err = block_on(gql.query::<Value>(Operation::new("{ x }"))).unwrap_err();
+        assert!(matches!(err, GraphQlError::Http(HttpError::Status { status: 400, .. })));
+    }
+
+    #[test]
+    fn persisted_query_resends_document_when_unknown() {
+        let mock = MockTransport::new()
+            .on(HttpMethod::POST, "/graphql", 200, r#"{"errors":[{"message":"PersistedQueryNotFound"}]}"#).once()
+            .on(HttpMethod::POST, "/graphql", 200, r#"{"data":{"x":1}}"#);
+        let gql = client(&mock).persisted_queries();
+
+        let data: Value = block_on(gql.query(Operation::new("{ x }"))).unwrap();
+        assert_eq!(data, json!({ "x": 1 }));
+        assert_eq!(mock.calls(HttpMethod::POST, "/graphql"), 2);
+        assert_eq!(sent_json(&mock)["query"], "{ x }");
+    }
+
+    #[test]
+    fn batch_rejects_response_count_mismatch() {
+        let mock = MockTransport::new().on(HttpMethod::POST, "/graphql", 200, r#"[{"data":{"a":1}}]"#);
+        let operations = [Operation::new("{ a }"), Operation::new("{ b }")];
+
+        let err = block_on(client(&mock).batch(&operations)).unwrap_err();
+        assert!(matches!(err, GraphQlError::Decode(msg) if msg == "1 respostas para 2 operações"));
+        assert_eq!(sent_json(&mock).as_array().map(Vec::len), Some(2));
+    }
+}
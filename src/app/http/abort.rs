@@ -0,0 +1,91 @@
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::rc::Rc;
+use futures::channel::oneshot;
+use sycamore::prelude::on_cleanup;
+use web_sys::AbortController;
+
+// --- CANCELAMENTO ---
+
+#[derive(Default)]
+struct AbortState {
+    aborted: Cell<bool>,
+    next_id: Cell<u32>,
+    // Controllers das tentativas em andamento
+    controllers: RefCell<Vec<(u32, AbortController)>>,
+    // Esperas (ex.: backoff entre tentativas) interrompidas pelo `abort()`
+    listeners: RefCell<Vec<oneshot::Sender<()>>>,
+}
+
+// Cancela as requisições ligadas a ele, inclusive as novas tentativas pendentes.
+// Um mesmo handle pode agrupar várias requisições (ex.: tudo que uma aba disparou).
+#[derive(Clone, Default)]
+pub struct AbortHandle {
+    state: Rc<AbortState>,
+}
+
+impl AbortHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Aborta sozinho quando o escopo reativo atual for descartado
+    // (componente desmontado, aba trocada no `TabView`...).
+    pub fn scoped() -> Self {
+        let handle = Self::new();
+        let on_dispose = handle.clone();
+        on_cleanup(move || on_dispose.abort());
+        handle
+    }
+
+    pub fn abort(&self) {
+        self.state.aborted.set(true);
+        for (_, controller) in self.state.controllers.take() {
+            controller.abort();
+        }
+        for listener in self.state.listeners.take() {
+            let _ = listener.send(());
+        }
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.state.aborted.get()
+    }
+
+    // Resolve quando o handle for abortado (na hora, se já foi).
+    pub(super) fn aborted(&self) -> impl Future<Output = ()> {
+        let (tx, rx) = oneshot::channel();
+        if self.is_aborted() {
+            let _ = tx.send(());
+        } else {
+            let mut listeners = self.state.listeners.borrow_mut();
+            listeners.retain(|listener| !listener.is_canceled());
+            listeners.push(tx);
+        }
+        async move {
+            let _ = rx.await;
+        }
+    }
+
+    // Liga o fetch ao handle enquanto o guard existir.
+    pub(super) fn attach(&self, controller: &AbortController) -> AbortGuard {
+        if self.is_aborted() {
+            controller.abort();
+        }
+        let id = self.state.next_id.get();
+        self.state.next_id.set(id.wrapping_add(1));
+        self.state.controllers.borrow_mut().push((id, controller.clone()));
+        AbortGuard { handle: self.clone(), id }
+    }
+}
+
+pub(super) struct AbortGuard {
+    handle: AbortHandle,
+    id: u32,
+}
+
+impl Drop for AbortGuard {
+    fn drop(&mut self) {
+        self.handle.state.controllers.borrow_mut().retain(|(id, _)| *id != self.id);
+    }
+}
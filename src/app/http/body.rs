@@ -0,0 +1,178 @@
+use serde::Serialize;
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+use web_sys::{Blob, BlobPropertyBag, File, FormData};
+
+// --- BODY ---
+
+#[derive(Debug, Clone)]
+pub enum RequestBody {
+    // JSON, texto puro ou `application/x-www-form-urlencoded`
+    Text(String),
+    // `multipart/form-data`; o navegador gera o boundary
+    Multipart(Multipart),
+}
+
+impl RequestBody {
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            RequestBody::Text(text) => Some(text),
+            RequestBody::Multipart(_) => None,
+        }
+    }
+
+    pub(super) fn to_js(&self) -> Result<JsValue, JsValue> {
+        match self {
+            RequestBody::Text(text) => Ok(JsValue::from_str(text)),
+            RequestBody::Multipart(multipart) => multipart.to_form_data().map(JsValue::from),
+        }
+    }
+}
+
+// --- MULTIPART ---
+
+#[derive(Debug, Clone)]
+pub enum Part {
+    Text(String),
+    Blob { blob: Blob, filename: Option<String> },
+    Bytes { data: Vec<u8>, filename: String, content_type: Option<String> },
+}
+
+// Upload de arquivos e formulários legados:
+//
+//     let form = Multipart::new()
+//         .text("descricao", "Logs de março")
+//         .file("arquivo", &file)
+//         .bytes("config", json.into_bytes(), "config.json", Some("application/json"));
+//     client.post("/upload").multipart(form).send().await?;
+#[derive(Debug, Clone, Default)]
+pub struct Multipart {
+    parts: Vec<(String, Part)>,
+}
+
+impl Multipart {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.parts.push((name.into(), Part::Text(value.into())));
+        self
+    }
+
+    // Usa o nome do próprio arquivo (ex.: vindo de um `<input type="file">`).
+    pub fn file(mut self, name: impl Into<String>, file: &File) -> Self {
+        let filename = Some(file.name());
+        self.parts.push((name.into(), Part::Blob { blob: file.clone().into(), filename }));
+        self
+    }
+
+    pub fn blob(mut self, name: impl Into<String>, blob: &Blob, filename: Option<&str>) -> Self {
+        let filename = filename.map(str::to_string);
+        self.parts.push((name.into(), Part::Blob { blob: blob.clone(), filename }));
+        self
+    }
+
+    pub fn bytes(
+        mut self,
+        name: impl Into<String>,
+        data: Vec<u8>,
+        filename: impl Into<String>,
+        content_type: Option<&str>,
+    ) -> Self {
+        let part = Part::Bytes { data, filename: filename.into(), content_type: content_type.map(str::to_string) };
+        self.parts.push((name.into(), part));
+        self
+    }
+
+    pub fn parts(&self) -> &[(String, Part)] {
+        &self.parts
+    }
+
+    fn to_form_data(&self) -> Result<FormData, JsValue> {
+        let form = FormData::new()?;
+        for (name, part) in &self.parts {
+            match part {
+                Part::Text(value) => form.append_with_str(name, value)?,
+                Part::Blob { blob, filename: Some(filename) } => form.append_with_blob_and_filename(name, blob, filename)?,
+                Part::Blob { blob, filename: None } => form.append_with_blob(name, blob)?,
+                Part::Bytes { data, filename, content_type } => {
+                    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(data.as_slice()));
+                    let options = BlobPropertyBag::new();
+                    if let Some(content_type) = content_type {
+                        options.set_type(content_type);
+                    }
+                    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options)?;
+                    form.append_with_blob_and_filename(name, &blob, filename)?;
+                }
+            }
+        }
+        Ok(form)
+    }
+}
+
+// --- URL ENCODING ---
+
+// Struct ou mapa -> `a=1&b=dois`. `None` é omitido, listas repetem a chave e
+// objetos aninhados vão como JSON.
+pub(super) fn url_encode<T: Serialize + ?Sized>(data: &T) -> Result<String, String> {
+    let Value::Object(fields) = serde_json::to_value(data).map_err(|e| e.to_string())? else {
+        return Err("esperado um struct ou mapa".to_string());
+    };
+    let mut encoder = form_urlencoded::Serializer::new(String::new());
+    for (key, value) in fields {
+        match value {
+            Value::Null => {}
+            Value::Array(items) => {
+                for item in items {
+                    encoder.append_pair(&key, &scalar(item));
+                }
+            }
+            value => {
+                encoder.append_pair(&key, &scalar(value));
+            }
+        }
+    }
+    Ok(encoder.finish())
+}
+
+fn scalar(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+// Acrescenta a query antes do `#fragmento`, respeitando uma query já existente.
+pub(super) fn append_query(url: &str, query: &str) -> String {
+    if query.is_empty() {
+        return url.to_string();
+    }
+    let (base, fragment) = match url.split_once('#') {
+        Some((base, fragment)) => (base, Some(fragment)),
+        None => (url, None),
+    };
+    let separator = match base.find('?') {
+        Some(i) if i + 1 < base.len() && !base.ends_with('&') => "&",
+        Some(_) => "",
+        None => "?",
+    };
+    match fragment {
+        Some(fragment) => format!("{}{}{}#{}", base, separator, query, fragment),
+        None => format!("{}{}{}", base, separator, query),
+    }
+}
+
+// `https://api/v1/` + `users` -> `https://api/v1/users`; URLs absolutas passam direto.
+pub(super) fn join_url(base: &str, path: &str) -> String {
+    if path.starts_with("http://") || path.starts_with("https://") || base.is_empty() {
+        return path.to_string();
+    }
+    if path.is_empty() {
+        return base.to_string();
+    }
+    match path.chars().next() {
+        Some('?') | Some('#') => format!("{}{}", base.trim_end_matches('/'), path),
+        _ => format!("{}/{}", base.trim_end_matches('/'), path.trim_start_matches('/')),
+    }
+}
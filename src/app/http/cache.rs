@@ -0,0 +1,327 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use futures::future::{FutureExt, LocalBoxFuture};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sycamore::futures::spawn_local;
+use web_sys::console;
+
+use super::idb::{idb_delete, idb_get, idb_keys, idb_put};
+use super::{js_message, HttpError, HttpMethod, HttpRequest, HttpResponse, HttpResult, Middleware, Next};
+
+// --- POLÍTICA ---
+
+// Diretivas de `Cache-Control` que importam para um cache privado.
+#[derive(Default)]
+struct CachePolicy {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<u32>,
+}
+
+fn cache_policy(headers: &HashMap<String, String>) -> CachePolicy {
+    let mut policy = CachePolicy::default();
+    let Some(value) = headers.get("cache-control") else { return policy };
+    for directive in value.split(',') {
+        let directive = directive.trim().to_ascii_lowercase();
+        match directive.split_once('=') {
+            Some(("max-age", secs)) => policy.max_age = secs.trim_matches('"').parse().ok(),
+            _ if directive == "no-store" => policy.no_store = true,
+            _ if directive == "no-cache" => policy.no_cache = true,
+            _ => {}
+        }
+    }
+    policy
+}
+
+// 2xx cacheáveis por padrão (RFC 9111); 206 exigiria juntar as faixas.
+fn is_cacheable_status(status: u16) -> bool {
+    matches!(status, 200 | 203 | 204)
+}
+
+fn request_header<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+}
+
+// Respostas autenticadas ficam separadas por credencial. Só um resumo do
+// `Authorization` entra na chave: o token não vai para o IndexedDB.
+fn cache_key(url: &str, headers: &HashMap<String, String>) -> String {
+    match request_header(headers, "authorization") {
+        Some(auth) => {
+            let digest: String = Sha256::digest(auth.as_bytes()).iter().take(8).map(|b| format!("{:02x}", b)).collect();
+            format!("{} {}", url, digest)
+        }
+        None => url.to_string(),
+    }
+}
+
+// A chave da própria URL ou de qualquer credencial usada com ela.
+fn is_url_key(key: &str, url: &str) -> bool {
+    key.strip_prefix(url).is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+}
+
+// --- ENTRADAS ---
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    status: u16,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+    stored_at: f64,
+    // `None` = revalida com o servidor antes de cada uso
+    max_age_ms: Option<f64>,
+    // Headers da requisição listados no `Vary`, com os valores enviados
+    #[serde(default)]
+    vary: Vec<(String, Option<String>)>,
+}
+
+impl CacheEntry {
+    // `None` quando não vale guardar: `no-store`, `Vary: *`, ou sem prazo nem validadores.
+    fn from_response(response: &HttpResponse, request_headers: &HashMap<String, String>) -> Option<Self> {
+        let mut vary = Vec::new();
+        for name in response.header("vary").unwrap_or_default().split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            if name == "*" {
+                return None;
+            }
+            let name = name.to_ascii_lowercase();
+            let value = request_header(request_headers, &name).map(str::to_string);
+            vary.push((name, value));
+        }
+        let policy = cache_policy(&response.headers);
+        let has_validators = response.header("etag").is_some() || response.header("last-modified").is_some();
+        let max_age_ms = policy.max_age
+            .filter(|_| !policy.no_cache)
+            .map(|secs| secs as f64 * 1000.0);
+        if policy.no_store || (max_age_ms.is_none() && !has_validators) {
+            return None;
+        }
+        Some(Self {
+            status: response.status,
+            headers: response.headers.clone(),
+            body: response.body.clone(),
+            stored_at: js_sys::Date::now(),
+            max_age_ms,
+            vary,
+        })
+    }
+
+    // A requisição manda os mesmos valores nos headers do `Vary`?
+    fn matches(&self, request_headers: &HashMap<String, String>) -> bool {
+        self.vary.iter().all(|(name, value)| request_header(request_headers, name) == value.as_deref())
+    }
+
+    fn is_fresh(&self) -> bool {
+        self.max_age_ms.is_some_and(|age| js_sys::Date::now() - self.stored_at < age)
+    }
+
+    // O 304 pode trazer `Cache-Control`/`ETag` novos; o body continua o guardado.
+    fn revalidated(mut self, headers: HashMap<String, String>, request_headers: &HashMap<String, String>) -> Option<Self> {
+        self.headers.extend(headers);
+        let response = self.response();
+        Some(Self { body: self.body, ..Self::from_response(&response, request_headers)? })
+    }
+
+    fn response(&self) -> HttpResponse {
+        HttpResponse { status: self.status, headers: self.headers.clone(), body: self.body.clone() }
+    }
+}
+
+// --- CACHE ---
+
+const STORE_PREFIX: &str = "http-cache:";
+
+#[derive(Default)]
+struct CacheState {
+    // Chave (URL + credencial) -> (último uso, entrada)
+    entries: HashMap<String, (u64, CacheEntry)>,
+    tick: u64,
+}
+
+// Cache de respostas GET para o `HttpClient`, ligado como middleware:
+//
+//     let cache = HttpCache::new(200).persistent();
+//     let api = HttpClient::new("https://api.local")
+//         .with_middleware(BearerAuth::new(token, refresh))
+//         .with_middleware(cache.clone());
+//     ...
+//     cache.purge("https://api.local/users");
+//
+// Dentro do `max-age` responde sem ir à rede; depois disso (ou com `no-cache`)
+// revalida com `If-None-Match`/`If-Modified-Since` e, num 304, devolve o body
+// guardado. POST/PUT/PATCH/DELETE bem-sucedidos descartam as entradas da mesma URL.
+// Respostas com `Authorization` são guardadas por credencial, e o `Vary` é respeitado.
+// A chave usa os headers que chegam até o cache: registre-o depois dos middlewares
+// que colocam credenciais (`BearerAuth`, `OnRequest`...), senão usuários diferentes
+// dividem a mesma entrada. Os headers padrão do cliente (`with_header`) já vêm na
+// requisição e sempre entram.
+#[derive(Clone)]
+pub struct HttpCache {
+    state: Rc<RefCell<CacheState>>,
+    capacity: usize,
+    persistent: bool,
+}
+
+impl HttpCache {
+    // Guarda até `capacity` respostas; acima disso sai a usada há mais tempo.
+    pub fn new(capacity: usize) -> Self {
+        Self { state: Rc::default(), capacity: capacity.max(1), persistent: false }
+    }
+
+    // Também grava no IndexedDB, sobrevivendo a recarregar a página.
+    pub fn persistent(mut self) -> Self {
+        self.persistent = true;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.borrow().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Remove as entradas cujas URLs começam com `prefix` (`""` limpa tudo).
+    pub fn purge(&self, prefix: &str) {
+        let prefix = prefix.to_string();
+        self.forget(move |key| key.starts_with(&prefix));
+    }
+
+    // Descarta as entradas de uma URL, de todas as credenciais.
+    fn remove_url(&self, url: &str) {
+        let url = url.to_string();
+        self.forget(move |key| is_url_key(key, &url));
+    }
+
+    fn forget(&self, matches: impl Fn(&str) -> bool + 'static) {
+        self.state.borrow_mut().entries.retain(|key, _| !matches(key));
+        if self.persistent {
+            spawn_local(async move {
+                let result = async {
+                    let keys = idb_keys().await?;
+                    let stored = keys.iter().filter_map(|key| Some((key, key.strip_prefix(STORE_PREFIX)?)));
+                    for (key, _) in stored.filter(|(_, key)| matches(key)) {
+                        idb_delete(key).await?;
+                    }
+                    Ok(())
+                };
+                if let Err(err) = result.await {
+                    console::warn_1(&format!("Cache HTTP: falha ao limpar ({})", js_message(&err)).into());
+                }
+            });
+        }
+    }
+
+    // Memória primeiro; no modo persistente, cai para o IndexedDB.
+    async fn lookup(&self, key: &str) -> Option<CacheEntry> {
+        {
+            let mut state = self.state.borrow_mut();
+            state.tick += 1;
+            let tick = state.tick;
+            if let Some((used, entry)) = state.entries.get_mut(key) {
+                *used = tick;
+                return Some(entry.clone());
+            }
+        }
+        if !self.persistent {
+            return None;
+        }
+        let raw = idb_get(&format!("{}{}", STORE_PREFIX, key)).await.ok().flatten()?;
+        let entry: CacheEntry = serde_json::from_str(&raw).ok()?;
+        self.insert(key, entry.clone(), false);
+        Some(entry)
+    }
+
+    fn insert(&self, key: &str, entry: CacheEntry, persist: bool) {
+        let evicted = {
+            let mut state = self.state.borrow_mut();
+            state.tick += 1;
+            let tick = state.tick;
+            state.entries.insert(key.to_string(), (tick, entry.clone()));
+            let mut evicted = Vec::new();
+            while state.entries.len() > self.capacity {
+                let Some(oldest) = state.entries.iter().min_by_key(|(_, (used, _))| *used).map(|(key, _)| key.clone()) else { break };
+                state.entries.remove(&oldest);
+                evicted.push(oldest);
+            }
+            evicted
+        };
+        if !self.persistent {
+            return;
+        }
+        let stored_key = format!("{}{}", STORE_PREFIX, key);
+        spawn_local(async move {
+            if persist {
+                if let Ok(contents) = serde_json::to_string(&entry) {
+                    let _ = idb_put(&stored_key, &contents).await;
+                }
+            }
+            for key in evicted {
+                let _ = idb_delete(&format!("{}{}", STORE_PREFIX, key)).await;
+            }
+        });
+    }
+
+    fn remove(&self, key: &str) {
+        self.state.borrow_mut().entries.remove(key);
+        if self.persistent {
+            let stored_key = format!("{}{}", STORE_PREFIX, key);
+            spawn_local(async move {
+                let _ = idb_delete(&stored_key).await;
+            });
+        }
+    }
+
+    fn store(&self, key: &str, entry: Option<CacheEntry>) {
+        match entry {
+            Some(entry) => self.insert(key, entry, true),
+            None => self.remove(key),
+        }
+    }
+}
+
+impl Middleware for HttpCache {
+    fn handle<'a>(&'a self, req: HttpRequest, next: Next<'a>) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        async move {
+            if req.method != HttpMethod::GET {
+                let url = req.url.clone();
+                let result = next.run(req).await;
+                if result.is_ok() {
+                    self.remove_url(&url);
+                }
+                return result;
+            }
+
+            let headers = req.headers.clone();
+            let key = cache_key(&req.url, &headers);
+            let cached = self.lookup(&key).await.filter(|entry| entry.matches(&headers));
+            let mut req = req;
+            if let Some(entry) = &cached {
+                if entry.is_fresh() {
+                    return Ok(entry.response());
+                }
+                if let Some(etag) = entry.headers.get("etag") {
+                    req = req.header("If-None-Match", etag.clone());
+                }
+                if let Some(modified) = entry.headers.get("last-modified") {
+                    req = req.header("If-Modified-Since", modified.clone());
+                }
+            }
+
+            match (next.run(req).await, cached) {
+                // O fetch entrega o 304 como erro de status (fora de 2xx)
+                (Err(HttpError::Status { status: 304, headers: response_headers, .. }), Some(entry)) => {
+                    let response = entry.response();
+                    self.store(&key, entry.revalidated(response_headers, &headers));
+                    Ok(response)
+                }
+                (Ok(response), _) if is_cacheable_status(response.status) => {
+                    self.store(&key, CacheEntry::from_response(&response, &headers));
+                    Ok(response)
+                }
+                (result, _) => result,
+            }
+        }.boxed_local()
+    }
+}
@@ -0,0 +1,76 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbRequest, IdbTransactionMode};
+
+// --- INDEXEDDB ---
+
+// Chave -> texto num único object store, compartilhado pela fila offline e pelo cache.
+const DB_NAME: &str = "sycamore-example";
+const DB_STORE: &str = "kv";
+
+// Espera o `success` (ou `error`) de um pedido ao IndexedDB.
+async fn idb_done(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+    JsFuture::from(promise).await.map_err(|_| {
+        request.error().ok().flatten().map(JsValue::from).unwrap_or_else(|| JsValue::from_str("IndexedDB: falha"))
+    })?;
+    request.result()
+}
+
+async fn idb_open() -> Result<IdbDatabase, JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("Sem acesso ao window"))?;
+    let factory = window
+        .indexed_db()?
+        .ok_or_else(|| JsValue::from_str("IndexedDB indisponível"))?;
+    let request = factory.open_with_u32(DB_NAME, 1)?;
+    let on_upgrade = Closure::once_into_js({
+        let request = request.clone();
+        move || {
+            if let Ok(db) = request.result() {
+                let _ = db.unchecked_into::<IdbDatabase>().create_object_store(DB_STORE);
+            }
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+    Ok(idb_done(&request).await?.unchecked_into())
+}
+
+pub(super) async fn idb_get(key: &str) -> Result<Option<String>, JsValue> {
+    let db = idb_open().await?;
+    let store = db.transaction_with_str(DB_STORE)?.object_store(DB_STORE)?;
+    let value = idb_done(&store.get(&JsValue::from_str(key))?).await?;
+    db.close();
+    Ok(value.as_string())
+}
+
+pub(super) async fn idb_put(key: &str, contents: &str) -> Result<(), JsValue> {
+    let db = idb_open().await?;
+    let store = db
+        .transaction_with_str_and_mode(DB_STORE, IdbTransactionMode::Readwrite)?
+        .object_store(DB_STORE)?;
+    idb_done(&store.put_with_key(&JsValue::from_str(contents), &JsValue::from_str(key))?).await?;
+    db.close();
+    Ok(())
+}
+
+pub(super) async fn idb_delete(key: &str) -> Result<(), JsValue> {
+    let db = idb_open().await?;
+    let store = db
+        .transaction_with_str_and_mode(DB_STORE, IdbTransactionMode::Readwrite)?
+        .object_store(DB_STORE)?;
+    idb_done(&store.delete(&JsValue::from_str(key))?).await?;
+    db.close();
+    Ok(())
+}
+
+pub(super) async fn idb_keys() -> Result<Vec<String>, JsValue> {
+    let db = idb_open().await?;
+    let store = db.transaction_with_str(DB_STORE)?.object_store(DB_STORE)?;
+    let keys: js_sys::Array = idb_done(&store.get_all_keys()?).await?.unchecked_into();
+    db.close();
+    Ok(keys.iter().filter_map(|key| key.as_string()).collect())
+}
@@ -0,0 +1,173 @@
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::rc::Rc;
+use futures::future::{FutureExt, LocalBoxFuture};
+use web_sys::console;
+
+use super::{HttpError, HttpRequest, HttpResponse, HttpResult};
+
+// --- PIPELINE ---
+
+// Cada middleware recebe a requisição e decide quando (e se) chamar o resto da cadeia.
+// Roda uma vez por `send()`, por fora das novas tentativas do `RetryPolicy`.
+pub trait Middleware {
+    fn handle<'a>(&'a self, req: HttpRequest, next: Next<'a>) -> LocalBoxFuture<'a, HttpResult<HttpResponse>>;
+}
+
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    chain: &'a [Rc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(super) fn new(chain: &'a [Rc<dyn Middleware>]) -> Self {
+        Self { chain }
+    }
+
+    pub fn run(self, req: HttpRequest) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        match self.chain.split_first() {
+            Some((middleware, rest)) => middleware.handle(req, Next { chain: rest }),
+            None => req.dispatch().boxed_local(),
+        }
+    }
+}
+
+// --- AJUSTES SIMPLES ---
+
+// Altera a requisição antes de enviar (headers, URL...).
+pub struct OnRequest<F>(pub F);
+
+impl<F: Fn(HttpRequest) -> HttpRequest> Middleware for OnRequest<F> {
+    fn handle<'a>(&'a self, req: HttpRequest, next: Next<'a>) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        next.run((self.0)(req))
+    }
+}
+
+// Transforma o resultado, ex.: converter um 404 em `Ok` ou reclassificar erros.
+pub struct OnResponse<F>(pub F);
+
+impl<F: Fn(HttpResult<HttpResponse>) -> HttpResult<HttpResponse>> Middleware for OnResponse<F> {
+    fn handle<'a>(&'a self, req: HttpRequest, next: Next<'a>) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        async move { (self.0)(next.run(req).await) }.boxed_local()
+    }
+}
+
+pub fn map_error<F>(f: F) -> OnResponse<impl Fn(HttpResult<HttpResponse>) -> HttpResult<HttpResponse>>
+where F: Fn(HttpError) -> HttpError {
+    OnResponse(move |result: HttpResult<HttpResponse>| result.map_err(&f))
+}
+
+// --- PRONTOS ---
+
+pub struct Logger;
+
+impl Middleware for Logger {
+    fn handle<'a>(&'a self, req: HttpRequest, next: Next<'a>) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        async move {
+            let label = format!("{} {}", req.method().as_str(), req.url());
+            let started = js_sys::Date::now();
+            let result = next.run(req).await;
+            let elapsed = js_sys::Date::now() - started;
+            match &result {
+                Ok(resp) => console::log_1(&format!("→ {} {} ({:.0}ms)", label, resp.status, elapsed).into()),
+                Err(e) => console::error_1(&format!("→ {} falhou: {} ({:.0}ms)", label, e, elapsed).into()),
+            }
+            result
+        }.boxed_local()
+    }
+}
+
+// Identificador único por requisição, útil para correlacionar com os logs do servidor.
+pub struct RequestId {
+    header: &'static str,
+    counter: Cell<u32>,
+}
+
+impl RequestId {
+    pub fn new(header: &'static str) -> Self {
+        Self { header, counter: Cell::new(0) }
+    }
+}
+
+impl Default for RequestId {
+    fn default() -> Self {
+        Self::new("X-Request-Id")
+    }
+}
+
+impl Middleware for RequestId {
+    fn handle<'a>(&'a self, req: HttpRequest, next: Next<'a>) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        let n = self.counter.get().wrapping_add(1);
+        self.counter.set(n);
+        let id = format!("{:x}-{:x}", js_sys::Date::now() as u64, n);
+        next.run(req.header(self.header, id))
+    }
+}
+
+// Mede cada requisição e repassa para um coletor de métricas.
+pub struct Timing<F>(pub F);
+
+pub struct RequestMetrics<'a> {
+    pub method: &'a str,
+    pub url: &'a str,
+    pub status: Option<u16>,
+    pub elapsed_ms: f64,
+}
+
+impl<F: Fn(RequestMetrics)> Middleware for Timing<F> {
+    fn handle<'a>(&'a self, req: HttpRequest, next: Next<'a>) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        async move {
+            let method = req.method().as_str().to_string();
+            let url = req.url().to_string();
+            let started = js_sys::Date::now();
+            let result = next.run(req).await;
+            let status = match &result {
+                Ok(resp) => Some(resp.status),
+                Err(e) => e.status(),
+            };
+            (self.0)(RequestMetrics { method: &method, url: &url, status, elapsed_ms: js_sys::Date::now() - started });
+            result
+        }.boxed_local()
+    }
+}
+
+// Envia o token atual e, num 401, pede um novo a `refresh` e repete a requisição uma vez.
+pub struct BearerAuth {
+    token: Rc<RefCell<Option<String>>>,
+    refresh: Rc<dyn Fn() -> LocalBoxFuture<'static, Option<String>>>,
+}
+
+impl BearerAuth {
+    pub fn new<F, Fut>(token: Option<String>, refresh: F) -> Self
+    where
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = Option<String>> + 'static,
+    {
+        Self {
+            token: Rc::new(RefCell::new(token)),
+            refresh: Rc::new(move || refresh().boxed_local()),
+        }
+    }
+
+    fn authorize(&self, req: HttpRequest) -> HttpRequest {
+        match self.token.borrow().as_ref() {
+            Some(token) => req.bearer(token.clone()),
+            None => req,
+        }
+    }
+}
+
+impl Middleware for BearerAuth {
+    fn handle<'a>(&'a self, req: HttpRequest, next: Next<'a>) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        async move {
+            let retry = req.clone();
+            let result = next.run(self.authorize(req)).await;
+            if !matches!(result, Err(HttpError::Status { status: 401, .. })) {
+                return result;
+            }
+            let Some(token) = (self.refresh)().await else { return result };
+            *self.token.borrow_mut() = Some(token);
+            next.run(self.authorize(retry)).await
+        }.boxed_local()
+    }
+}
@@ -1,12 +1,37 @@
+mod abort;
+mod body;
+mod cache;
+mod idb;
+mod middleware;
+mod offline;
+mod proxy;
+mod response;
+mod sse;
+mod transport;
+
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use wasm_bindgen::JsCast;
+use std::rc::Rc;
+use futures::future::{FutureExt, LocalBoxFuture};
+use wasm_bindgen::prelude::*;
+#[cfg(target_arch = "wasm32")]
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{Request, RequestInit, RequestMode, Response, Headers};
+
+pub use abort::AbortHandle;
+use abort::AbortGuard;
+pub use body::{Multipart, Part, RequestBody};
+pub use cache::HttpCache;
+pub use middleware::*;
+pub use offline::{create_offline_queue, use_offline_queue, OfflineQueue};
+pub use proxy::TauriProxyTransport;
+pub use response::*;
+pub use sse::{subscribe, EventSource, SseEvent, SseState};
+pub use transport::*;
 
 // --- TIPOS ---
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum HttpMethod {
     GET,
     POST,
@@ -16,7 +41,12 @@ pub enum HttpMethod {
 }
 
 impl HttpMethod {
-    fn as_str(&self) -> &str {
+    // Repetir estes não muda o resultado no servidor
+    fn is_idempotent(&self) -> bool {
+        !matches!(self, HttpMethod::POST | HttpMethod::PATCH)
+    }
+
+    pub fn as_str(&self) -> &str {
         match self {
             HttpMethod::GET => "GET",
             HttpMethod::POST => "POST",
@@ -28,36 +58,201 @@ impl HttpMethod {
 }
 
 #[derive(Debug, Clone)]
-pub struct HttpError {
-    pub status: u16,
-    pub message: String,
+pub enum HttpError {
+    // Sem conexão, DNS, CORS... (mensagem original do navegador)
+    Network(String),
+    Timeout(u32),
+    Aborted,
+    Status {
+        status: u16,
+        headers: HashMap<String, String>,
+        body: String,
+    },
+    Decode(String),
+    InvalidRequest(String),
+    // Sem rede: guardada na `OfflineQueue` e reenviada ao reconectar
+    Queued,
+}
+
+impl HttpError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            HttpError::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    pub fn is_offline(&self) -> bool {
+        matches!(self, HttpError::Network(_))
+            && web_sys::window().is_some_and(|w| !w.navigator().on_line())
+    }
+
+    // Falhas transitórias que valem uma nova tentativa.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            HttpError::Network(_) | HttpError::Timeout(_) => true,
+            HttpError::Status { status, .. } => is_retryable_status(*status),
+            _ => false,
+        }
+    }
 }
 
 impl From<serde_json::Error> for HttpError {
     fn from(err: serde_json::Error) -> Self {
-        HttpError {
-            status: 0,
-            message: format!("JSON parse error: {}", err),
-        }
+        HttpError::Decode(err.to_string())
     }
 }
 
 impl std::fmt::Display for HttpError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "HTTP {} - {}", self.status, self.message)
+        match self {
+            HttpError::Network(msg) => write!(f, "Falha de rede: {}", msg),
+            HttpError::Timeout(ms) => write!(f, "Tempo esgotado após {}ms", ms),
+            HttpError::Aborted => write!(f, "Requisição cancelada"),
+            HttpError::Status { status, body, .. } => write!(f, "HTTP {} - {}", status, body),
+            HttpError::Decode(msg) => write!(f, "Resposta inválida: {}", msg),
+            HttpError::InvalidRequest(msg) => write!(f, "Requisição inválida: {}", msg),
+            HttpError::Queued => write!(f, "Sem conexão; a alteração será enviada ao reconectar"),
+        }
     }
 }
 
+impl std::error::Error for HttpError {}
+
+// Mensagem de um erro JS (`Error`, `DOMException` ou valor qualquer).
+fn js_message(err: &JsValue) -> String {
+    js_sys::Reflect::get(err, &JsValue::from_str("message"))
+        .ok()
+        .and_then(|m| m.as_string())
+        .filter(|m| !m.is_empty())
+        .or_else(|| err.as_string())
+        .unwrap_or_else(|| format!("{:?}", err))
+}
+
+fn js_name(err: &JsValue) -> Option<String> {
+    js_sys::Reflect::get(err, &JsValue::from_str("name")).ok().and_then(|n| n.as_string())
+}
+
 pub type HttpResult<T> = Result<T, HttpError>;
 
+// --- RETRY ---
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    // Total de tentativas, contando a primeira
+    pub max_attempts: u32,
+    pub base_delay_ms: u32,
+    pub max_delay_ms: u32,
+    // POST/PATCH só são repetidos se isto for `false`
+    pub idempotent_only: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_ms: 300,
+            max_delay_ms: 10_000,
+            idempotent_only: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn delay(mut self, base_ms: u32, max_ms: u32) -> Self {
+        self.base_delay_ms = base_ms;
+        self.max_delay_ms = max_ms.max(base_ms);
+        self
+    }
+
+    pub fn any_method(mut self) -> Self {
+        self.idempotent_only = false;
+        self
+    }
+
+    fn backoff(&self, attempt: u32) -> u32 {
+        backoff_delay(self.base_delay_ms, self.max_delay_ms, attempt)
+    }
+}
+
+// Backoff exponencial com jitter: sorteia entre metade e o total do atraso.
+// `attempt` começa em 1.
+pub(crate) fn backoff_delay(base_ms: u32, max_ms: u32, attempt: u32) -> u32 {
+    let exp = base_ms.saturating_mul(1 << attempt.saturating_sub(1).min(16));
+    let delay = exp.min(max_ms) as f64;
+    (delay / 2.0 + jitter() * delay / 2.0) as u32
+}
+
+#[cfg(target_arch = "wasm32")]
+fn jitter() -> f64 {
+    js_sys::Math::random()
+}
+
+// Fora do navegador (testes nativos) não há `Math.random`: atraso cheio, previsível.
+#[cfg(not(target_arch = "wasm32"))]
+fn jitter() -> f64 {
+    1.0
+}
+
+fn is_retryable_status(status: u16) -> bool {
+    status == 429 || status == 408 || (500..600).contains(&status)
+}
+
+// `Retry-After` vem em segundos ou como data HTTP.
+fn parse_retry_after(value: &str) -> Option<u32> {
+    if let Ok(secs) = value.trim().parse::<u32>() {
+        return Some(secs.saturating_mul(1000));
+    }
+    let at = js_sys::Date::parse(value);
+    if at.is_nan() {
+        return None;
+    }
+    Some((at - js_sys::Date::now()).max(0.0) as u32)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(ms: u32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms as i32);
+        }
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+thread_local! {
+    // Esperas pedidas fora do navegador, para os testes conferirem os atrasos
+    pub(crate) static SLEEPS: std::cell::RefCell<Vec<u32>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+// Sem timers nativos: a espera termina na hora e só fica registrada.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(ms: u32) {
+    SLEEPS.with(|sleeps| sleeps.borrow_mut().push(ms));
+}
+
 // --- REQUEST BUILDER ---
 
+#[derive(Clone)]
 pub struct HttpRequest {
     url: String,
     method: HttpMethod,
     headers: HashMap<String, String>,
-    body: Option<String>,
+    body: Option<RequestBody>,
+    // Erro de montagem (ex.: serialização), devolvido no envio
+    invalid: Option<String>,
     timeout: Option<u32>,
+    retry: Option<RetryPolicy>,
+    middleware: Vec<Rc<dyn Middleware>>,
+    transport: Rc<dyn Transport>,
+    progress: Option<Rc<dyn Fn(Progress)>>,
+    abort: Option<AbortHandle>,
 }
 
 impl HttpRequest {
@@ -67,10 +262,24 @@ impl HttpRequest {
             method,
             headers: HashMap::new(),
             body: None,
+            invalid: None,
             timeout: None,
+            retry: None,
+            middleware: Vec::new(),
+            transport: Rc::new(FetchTransport),
+            progress: None,
+            abort: None,
         }
     }
 
+    pub fn method(&self) -> &HttpMethod {
+        &self.method
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
     pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.headers.insert(key.into(), value.into());
         self
@@ -80,120 +289,179 @@ impl HttpRequest {
         self.header("Authorization", format!("Bearer {}", token.into()))
     }
 
+    fn invalid(mut self, message: String) -> Self {
+        self.invalid.get_or_insert(message);
+        self
+    }
+
+    fn without_content_type(mut self) -> Self {
+        self.headers.retain(|key, _| !key.eq_ignore_ascii_case("content-type"));
+        self
+    }
+
     pub fn json<T: Serialize>(mut self, data: &T) -> Self {
-        if let Ok(json_str) = serde_json::to_string(data) {
-            self.body = Some(json_str);
-            self.header("Content-Type", "application/json")
-        } else {
-            self
+        match serde_json::to_string(data) {
+            Ok(json_str) => {
+                self.body = Some(RequestBody::Text(json_str));
+                self.without_content_type().header("Content-Type", "application/json")
+            }
+            Err(e) => self.invalid(format!("JSON: {}", e)),
         }
     }
 
     pub fn body(mut self, data: impl Into<String>) -> Self {
-        self.body = Some(data.into());
+        self.body = Some(RequestBody::Text(data.into()));
         self
     }
 
+    // `application/x-www-form-urlencoded`, a partir de um struct ou mapa.
+    pub fn form<T: Serialize + ?Sized>(mut self, data: &T) -> Self {
+        match body::url_encode(data) {
+            Ok(encoded) => {
+                self.body = Some(RequestBody::Text(encoded));
+                self.without_content_type().header("Content-Type", "application/x-www-form-urlencoded")
+            }
+            Err(e) => self.invalid(format!("formulário: {}", e)),
+        }
+    }
+
+    // Sem `Content-Type` manual: o navegador define o boundary.
+    pub fn multipart(mut self, form: Multipart) -> Self {
+        self.body = Some(RequestBody::Multipart(form));
+        self.without_content_type()
+    }
+
+    // Ex.: `.query(&Filtro { pagina: 2, tags: vec!["a", "b"] })` -> `?pagina=2&tags=a&tags=b`
+    pub fn query<T: Serialize + ?Sized>(mut self, params: &T) -> Self {
+        match body::url_encode(params) {
+            Ok(encoded) => {
+                self.url = body::append_query(&self.url, &encoded);
+                self
+            }
+            Err(e) => self.invalid(format!("query: {}", e)),
+        }
+    }
+
+    // Vale por tentativa; o fetch é abortado via `AbortController`.
     pub fn timeout(mut self, ms: u32) -> Self {
         self.timeout = Some(ms);
         self
     }
 
-    pub async fn send(self) -> HttpResult<HttpResponse> {
-        let window = web_sys::window().ok_or_else(|| HttpError {
-            status: 0,
-            message: "Sem acesso ao window".to_string(),
-        })?;
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
 
-        let opts = RequestInit::new();
-        opts.set_method(self.method.as_str());
-        opts.set_mode(RequestMode::Cors);
+    // Roda depois dos middlewares do cliente, na ordem em que foram registrados.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Rc::new(middleware));
+        self
+    }
 
-        if let Some(body) = self.body {
-            opts.set_body(&wasm_bindgen::JsValue::from_str(&body));
-        }
+    // Troca o `fetch` por outro transporte, ex.: `MockTransport` nos testes.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Rc::new(transport);
+        self
+    }
 
-        let request = Request::new_with_str_and_init(&self.url, &opts).map_err(|_| HttpError {
-            status: 0,
-            message: "Falha ao criar request".to_string(),
-        })?;
-
-        let headers = request.headers();
-
-        // Adiciona headers
-        for (key, value) in self.headers {
-            headers
-                .set(&key, &value)
-                .map_err(|_| HttpError {
-                    status: 0,
-                    message: format!("Falha ao definir header: {}", key),
-                })?;
-        }
+    // Chamado a cada pedaço do body recebido, ex.: para alimentar um `ProgressBar`.
+    pub fn on_progress(mut self, callback: impl Fn(Progress) + 'static) -> Self {
+        self.progress = Some(Rc::new(callback));
+        self
+    }
 
-        // Faz o fetch
-        let resp_value = JsFuture::from(window.fetch_with_request(&request))
-            .await
-            .map_err(|_| HttpError {
-                status: 0,
-                message: "Falha na requisição".to_string(),
-            })?;
-
-        let response: Response = resp_value.dyn_into().map_err(|_| HttpError {
-            status: 0,
-            message: "Resposta inválida".to_string(),
-        })?;
-
-        let status = response.status() as u16;
-        let ok = response.ok();
-
-        // Lê o body
-        let text = if let Ok(text_promise) = response.text() {
-            JsFuture::from(text_promise)
-                .await
-                .ok()
-                .and_then(|v| v.as_string())
-                .unwrap_or_default()
-        } else {
-            String::new()
-        };
-
-        if !ok {
-            return Err(HttpError {
-                status,
-                message: text,
-            });
-        }
+    // Liga a requisição a um handle que pode cancelá-la a qualquer momento.
+    pub fn abort_with(mut self, handle: &AbortHandle) -> Self {
+        self.abort = Some(handle.clone());
+        self
+    }
 
-        Ok(HttpResponse { status, body: text })
+    // Como `send`, mas devolve também o handle de cancelamento. O handle já vem
+    // ligado ao escopo reativo atual: desmontar o componente aborta a requisição.
+    pub fn send_cancellable(self) -> (AbortHandle, LocalBoxFuture<'static, HttpResult<HttpResponse>>) {
+        let handle = AbortHandle::scoped();
+        (handle.clone(), self.abort_with(&handle).send().boxed_local())
     }
-}
 
-// --- RESPONSE ---
+    pub async fn send(mut self) -> HttpResult<HttpResponse> {
+        let chain = std::mem::take(&mut self.middleware);
+        Next::new(&chain).run(self).await
+    }
 
-pub struct HttpResponse {
-    pub status: u16,
-    pub body: String,
-}
+    // Entrega a resposta assim que chegam os headers, com o body lido sob demanda.
+    // Sem middlewares nem novas tentativas: o body só pode ser consumido uma vez.
+    pub async fn stream(self) -> HttpResult<StreamingResponse> {
+        if let Some(message) = &self.invalid {
+            return Err(HttpError::InvalidRequest(message.clone()));
+        }
+        if self.is_aborted() {
+            return Err(HttpError::Aborted);
+        }
+        self.transport.stream(&self).await
+    }
 
-impl HttpResponse {
-    pub fn json<T: for<'de> Deserialize<'de>>(&self) -> Result<T, serde_json::Error> {
-        serde_json::from_str(&self.body)
+    // Mantém a conexão aberta recebendo `text/event-stream`; ver `EventSource`.
+    pub fn event_source(self) -> EventSource {
+        EventSource::from_request(self)
     }
 
-    pub fn text(&self) -> &str {
-        &self.body
+    fn is_aborted(&self) -> bool {
+        self.abort.as_ref().is_some_and(AbortHandle::is_aborted)
     }
 
-    pub fn is_success(&self) -> bool {
-        self.status >= 200 && self.status < 300
+    // Fim da cadeia de middlewares: envia com as novas tentativas do `RetryPolicy`.
+    async fn dispatch(self) -> HttpResult<HttpResponse> {
+        if let Some(message) = &self.invalid {
+            return Err(HttpError::InvalidRequest(message.clone()));
+        }
+        let policy = self.retry
+            .filter(|p| !p.idempotent_only || self.method.is_idempotent())
+            .unwrap_or(RetryPolicy { max_attempts: 1, ..RetryPolicy::default() });
+
+        let mut attempt = 1;
+        loop {
+            if self.is_aborted() {
+                return Err(HttpError::Aborted);
+            }
+            let error = match self.transport.send(&self).await {
+                Ok(response) => return Ok(response),
+                Err(_) if self.is_aborted() => return Err(HttpError::Aborted),
+                Err(error) => error,
+            };
+            if !error.is_retryable() || attempt >= policy.max_attempts {
+                return Err(error);
+            }
+            let retry_after = match &error {
+                HttpError::Status { headers, .. } => headers.get("retry-after").and_then(|v| parse_retry_after(v)),
+                _ => None,
+            };
+            let delay = policy.backoff(attempt).max(retry_after.unwrap_or(0));
+            // Um abort durante a espera encerra na hora, sem esperar o backoff
+            match &self.abort {
+                Some(handle) => {
+                    let wait = sleep(delay).boxed_local();
+                    let aborted = handle.aborted().boxed_local();
+                    futures::future::select(wait, aborted).await;
+                }
+                None => sleep(delay).await,
+            }
+            attempt += 1;
+        }
     }
 }
 
 // --- HTTP CLIENT ---
 
+#[derive(Clone)]
 pub struct HttpClient {
     base_url: String,
     default_headers: HashMap<String, String>,
+    default_timeout: Option<u32>,
+    default_retry: Option<RetryPolicy>,
+    middleware: Vec<Rc<dyn Middleware>>,
+    transport: Rc<dyn Transport>,
+    abort: Option<AbortHandle>,
 }
 
 impl HttpClient {
@@ -201,6 +469,11 @@ impl HttpClient {
         Self {
             base_url: base_url.into(),
             default_headers: HashMap::new(),
+            default_timeout: None,
+            default_retry: None,
+            middleware: Vec::new(),
+            transport: Rc::new(FetchTransport),
+            abort: None,
         }
     }
 
@@ -213,19 +486,43 @@ impl HttpClient {
         self.with_header("Authorization", format!("Bearer {}", token.into()))
     }
 
-    fn build_url(&self, path: &str) -> String {
-        if path.starts_with("http://") || path.starts_with("https://") {
-            path.to_string()
-        } else {
-            format!("{}{}", self.base_url.trim_end_matches('/'), path)
-        }
+    pub fn with_timeout(mut self, ms: u32) -> Self {
+        self.default_timeout = Some(ms);
+        self
+    }
+
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.default_retry = Some(policy);
+        self
+    }
+
+    // Ex.: `.with_middleware(Logger).with_middleware(RequestId::default())`
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Rc::new(middleware));
+        self
+    }
+
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Rc::new(transport);
+        self
+    }
+
+    // Todas as requisições do cliente passam a ser canceladas juntas pelo handle.
+    pub fn with_abort(mut self, handle: &AbortHandle) -> Self {
+        self.abort = Some(handle.clone());
+        self
     }
 
     fn create_request(&self, method: HttpMethod, path: &str) -> HttpRequest {
-        let mut req = HttpRequest::new(method, self.build_url(path));
+        let mut req = HttpRequest::new(method, body::join_url(&self.base_url, path));
         for (k, v) in &self.default_headers {
             req = req.header(k, v);
         }
+        req.timeout = self.default_timeout;
+        req.retry = self.default_retry;
+        req.middleware = self.middleware.clone();
+        req.transport = self.transport.clone();
+        req.abort = self.abort.clone();
         req
     }
 
@@ -262,10 +559,7 @@ pub async fn post(url: impl Into<String>) -> HttpRequest {
 
 pub async fn get_json<T: for<'de> Deserialize<'de>>(url: impl Into<String>) -> HttpResult<T> {
     let resp = get(url).await?;
-    resp.json().map_err(|e| HttpError {
-        status: 0,
-        message: format!("JSON parse error: {}", e),
-    })
+    Ok(resp.json()?)
 }
 
 pub async fn post_json<B: Serialize, T: for<'de> Deserialize<'de>>(
@@ -277,8 +571,5 @@ pub async fn post_json<B: Serialize, T: for<'de> Deserialize<'de>>(
         .send()
         .await?;
     
-    resp.json().map_err(|e| HttpError {
-        status: 0,
-        message: format!("JSON parse error: {}", e),
-    })
+    Ok(resp.json()?)
 }
\ No newline at end of file
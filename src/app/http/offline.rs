@@ -0,0 +1,492 @@
+use std::collections::HashMap;
+use futures::future::{FutureExt, LocalBoxFuture};
+use serde::{Deserialize, Serialize};
+use sycamore::futures::spawn_local;
+use sycamore::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::console;
+
+use super::idb::{idb_get, idb_put};
+use super::{backoff_delay, is_retryable_status, js_message, sleep, HttpClient, HttpError, HttpMethod, HttpRequest, HttpResponse, HttpResult, Middleware, Next, RequestBody};
+use crate::tauri_bridge;
+
+// --- FILA ---
+
+const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
+// Tentativas seguidas de reenvio de um item; esgotadas, ele espera o próximo `online`
+const MAX_REPLAY_ATTEMPTS: u32 = 5;
+
+// Requisição guardada até a rede voltar. Só bodies de texto (JSON, form) são aceitos.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QueuedRequest {
+    key: String,
+    method: HttpMethod,
+    url: String,
+    headers: HashMap<String, String>,
+    body: Option<String>,
+    queued_at: f64,
+}
+
+impl QueuedRequest {
+    // Passa de novo pelo cliente: transporte, timeout, retry e middlewares dele.
+    fn to_request(&self, client: &HttpClient) -> HttpRequest {
+        let mut req = client.create_request(self.method.clone(), &self.url);
+        req.headers.extend(self.headers.clone());
+        req.body = self.body.clone().map(RequestBody::Text);
+        req
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Snapshot {
+    items: Vec<QueuedRequest>,
+    last_sync: Option<f64>,
+}
+
+fn is_mutating(method: &HttpMethod) -> bool {
+    !matches!(method, HttpMethod::GET)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn is_online() -> bool {
+    web_sys::window().is_none_or(|w| w.navigator().on_line())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn idempotency_key() -> String {
+    let random = (js_sys::Math::random() * u32::MAX as f64) as u32;
+    format!("{:x}-{:08x}", js_sys::Date::now() as u64, random)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn warn(message: &str) {
+    console::warn_1(&message.into());
+}
+
+// Fora do navegador (testes nativos): sempre online, relógio e chaves do sistema.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_online() -> bool {
+    true
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |d| d.as_millis() as f64)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn idempotency_key() -> String {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    format!("{:x}-{:08x}", now() as u64, COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn warn(message: &str) {
+    eprintln!("{}", message);
+}
+
+// Fila opcional para POST/PUT/PATCH/DELETE. Ligada como middleware, guarda o que
+// falhar por erro de rede (devolvendo `HttpError::Queued`) e reenvia na ordem,
+// pelo cliente de `replay_through`, quando a conexão voltar:
+//
+//     let offline = create_offline_queue();
+//     let api = HttpClient::new("https://api.local").with_middleware(offline);
+//     offline.replay_through(&api);
+//     ...
+//     Label(text = format!("{} pendentes", offline.pending_count.get()))
+//
+// Cada requisição leva um `Idempotency-Key` fixo, repetido no reenvio, para o
+// servidor ignorar duplicatas. A fila sobrevive a recarregar a página: fica no
+// IndexedDB (navegador) ou num arquivo do backend (Tauri).
+#[derive(Clone, Copy)]
+pub struct OfflineQueue {
+    items: Signal<Vec<QueuedRequest>>,
+    pub pending_count: ReadSignal<usize>,
+    // Último envio bem-sucedido (ms desde a época), direto ou por reenvio
+    pub last_sync: Signal<Option<f64>>,
+    replaying: Signal<bool>,
+    client: Signal<Option<HttpClient>>,
+    // Até carregar o que estava salvo, nada é gravado por cima
+    loaded: Signal<bool>,
+    saving: Signal<bool>,
+    dirty: Signal<bool>,
+}
+
+pub fn create_offline_queue() -> OfflineQueue {
+    let queue = OfflineQueue::new();
+    provide_context(queue);
+
+    spawn_local(async move {
+        let snapshot = match load_snapshot().await {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                warn(&format!("Fila offline: não foi possível carregar ({})", err));
+                Snapshot::default()
+            }
+        };
+        // O que foi enfileirado antes de carregar vai para o fim
+        queue.items.update(|items| {
+            let newer = std::mem::replace(items, snapshot.items);
+            items.extend(newer);
+        });
+        if queue.last_sync.with_untracked(Option::is_none) {
+            queue.last_sync.set(snapshot.last_sync);
+        }
+        queue.loaded.set(true);
+        queue.persist();
+        queue.replay();
+    });
+
+    let on_online = Closure::<dyn Fn()>::new(move || queue.replay());
+    let window = window();
+    let _ = window.add_event_listener_with_callback("online", on_online.as_ref().unchecked_ref());
+    on_cleanup(move || {
+        let _ = window.remove_event_listener_with_callback("online", on_online.as_ref().unchecked_ref());
+    });
+
+    queue
+}
+
+pub fn use_offline_queue() -> OfflineQueue {
+    use_context::<OfflineQueue>()
+}
+
+impl OfflineQueue {
+    // Só os sinais; até `loaded`, nada é salvo nem reenviado.
+    fn new() -> Self {
+        let items = create_signal(Vec::<QueuedRequest>::new());
+        OfflineQueue {
+            items,
+            pending_count: create_memo(move || items.with(Vec::len)),
+            last_sync: create_signal(None),
+            replaying: create_signal(false),
+            client: create_signal(None),
+            loaded: create_signal(false),
+            saving: create_signal(false),
+            dirty: create_signal(false),
+        }
+    }
+
+    fn enqueue(self, req: &HttpRequest, key: String) -> HttpResult<HttpResponse> {
+        let body = match &req.body {
+            None => None,
+            Some(RequestBody::Text(text)) => Some(text.clone()),
+            Some(RequestBody::Multipart(_)) => {
+                return Err(HttpError::InvalidRequest("multipart não pode ir para a fila offline".to_string()));
+            }
+        };
+        self.items.update(|items| items.push(QueuedRequest {
+            key,
+            method: req.method.clone(),
+            url: req.url.clone(),
+            headers: req.headers.clone(),
+            body,
+            queued_at: now(),
+        }));
+        self.persist();
+        self.replay();
+        Err(HttpError::Queued)
+    }
+
+    fn synced(self) {
+        self.last_sync.set(Some(now()));
+        self.persist();
+    }
+
+    // Descarta tudo que ainda não foi enviado.
+    pub fn clear(self) {
+        self.items.set(Vec::new());
+        self.persist();
+    }
+
+    // Cliente usado nos reenvios; deve ser o mesmo que tem esta fila como middleware.
+    // Até ser informado, o que estiver na fila fica esperando.
+    pub fn replay_through(self, client: &HttpClient) {
+        self.client.set(Some(client.clone()));
+        self.replay();
+    }
+
+    // Reenvia o que estiver na fila, se já carregou e tem cliente.
+    pub fn replay(self) {
+        if !self.loaded.get_untracked() || self.replaying.get_untracked() {
+            return;
+        }
+        let Some(client) = self.client.get_clone_untracked() else { return };
+        self.replaying.set(true);
+        spawn_local(async move { self.replay_pending(&client).await });
+    }
+
+    // Do início da fila, uma por vez. Sem rede, para até o evento `online`. Falhas de
+    // rede, timeouts e 5xx repetem o mesmo item com backoff; depois de
+    // `MAX_REPLAY_ATTEMPTS` ele fica na fila. Só respostas 4xx descartam o item.
+    async fn replay_pending(self, client: &HttpClient) {
+        self.replaying.set(true);
+        let mut attempt = 0;
+        while let Some(item) = self.items.with_untracked(|items| items.first().cloned()) {
+            if !is_online() {
+                break;
+            }
+            match item.to_request(client).send().await {
+                Ok(_) => {
+                    attempt = 0;
+                    self.remove(&item.key);
+                    self.synced();
+                }
+                Err(err @ HttpError::Status { status, .. }) if (400..500).contains(&status) && !is_retryable_status(status) => {
+                    attempt = 0;
+                    warn(&format!("Fila offline: {} {} descartada: {}", item.method.as_str(), item.url, err));
+                    self.remove(&item.key);
+                }
+                Err(_) if attempt + 1 >= MAX_REPLAY_ATTEMPTS => break,
+                Err(_) => {
+                    attempt += 1;
+                    sleep(backoff_delay(1_000, 60_000, attempt)).await;
+                }
+            }
+        }
+        self.replaying.set(false);
+    }
+
+    // O reenvio volta pelo próprio middleware; o item da vez segue direto.
+    fn is_replaying(self, key: &str) -> bool {
+        self.replaying.get_untracked()
+            && self.items.with_untracked(|items| items.first().is_some_and(|item| item.key == key))
+    }
+
+    fn remove(self, key: &str) {
+        self.items.update(|items| items.retain(|item| item.key != key));
+        self.persist();
+    }
+
+    // Grava o estado atual; gravações seguidas se juntam numa só.
+    fn persist(self) {
+        if !self.loaded.get_untracked() {
+            return;
+        }
+        self.dirty.set(true);
+        if self.saving.get_untracked() {
+            return;
+        }
+        self.saving.set(true);
+        spawn_local(async move {
+            while self.dirty.get_untracked() {
+                self.dirty.set(false);
+                let snapshot = Snapshot {
+                    items: self.items.get_clone_untracked(),
+                    last_sync: self.last_sync.get_untracked(),
+                };
+                if let Err(err) = save_snapshot(&snapshot).await {
+                    warn(&format!("Fila offline: não foi possível salvar ({})", err));
+                }
+            }
+            self.saving.set(false);
+        });
+    }
+}
+
+impl Middleware for OfflineQueue {
+    fn handle<'a>(&'a self, req: HttpRequest, next: Next<'a>) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        let queue = *self;
+        async move {
+            if !is_mutating(&req.method) {
+                return next.run(req).await;
+            }
+            let key = req.headers.get(IDEMPOTENCY_HEADER).cloned().unwrap_or_else(idempotency_key);
+            if queue.is_replaying(&key) {
+                return next.run(req).await;
+            }
+            let req = req.header(IDEMPOTENCY_HEADER, key.clone());
+
+            // Mantém a ordem: com itens pendentes, as novas entram atrás deles
+            if !is_online() || queue.items.with_untracked(|items| !items.is_empty()) {
+                return queue.enqueue(&req, key);
+            }
+            match next.run(req.clone()).await {
+                Ok(resp) => {
+                    queue.synced();
+                    Ok(resp)
+                }
+                // `navigator.onLine` continua `true` numa rede sem internet ou atrás
+                // de um captive portal; qualquer falha de rede vai para a fila
+                Err(HttpError::Network(_)) => queue.enqueue(&req, key),
+                Err(err) => Err(err),
+            }
+        }.boxed_local()
+    }
+}
+
+// --- PERSISTÊNCIA ---
+
+const STORAGE_KEY: &str = "offline-queue";
+
+#[derive(Serialize)]
+struct LoadArgs<'a> {
+    key: &'a str,
+}
+
+#[derive(Serialize)]
+struct SaveArgs<'a> {
+    key: &'a str,
+    contents: &'a str,
+}
+
+async fn load_snapshot() -> Result<Snapshot, String> {
+    let raw: Option<String> = if tauri_bridge::is_tauri() {
+        tauri_bridge::invoke("load_settings", &LoadArgs { key: STORAGE_KEY }).await.map_err(|e| e.to_string())?
+    } else {
+        idb_get(STORAGE_KEY).await.map_err(|e| js_message(&e))?
+    };
+    match raw {
+        Some(raw) => serde_json::from_str(&raw).map_err(|e| e.to_string()),
+        None => Ok(Snapshot::default()),
+    }
+}
+
+async fn save_snapshot(snapshot: &Snapshot) -> Result<(), String> {
+    let contents = serde_json::to_string(snapshot).map_err(|e| e.to_string())?;
+    if tauri_bridge::is_tauri() {
+        tauri_bridge::invoke("save_settings", &SaveArgs { key: STORAGE_KEY, contents: &contents })
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        idb_put(STORAGE_KEY, &contents).await.map_err(|e| js_message(&e))
+    }
+}
+
+// Nativos (`cargo test`); no wasm32 o `sleep` é o timer de verdade.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::app::http::{MockTransport, SLEEPS};
+
+    fn take_sleeps() -> Vec<u32> {
+        SLEEPS.with(|sleeps| sleeps.take())
+    }
+
+    // Fila sem `loaded`: não persiste nem dispara reenvio sozinha
+    fn setup(mock: &MockTransport, test: impl FnOnce(OfflineQueue, HttpClient)) {
+        let root = create_root(|| {
+            let queue = OfflineQueue::new();
+            let client = HttpClient::new("https://api.local")
+                .with_middleware(queue)
+                .with_transport(mock.clone());
+            test(queue, client);
+        });
+        root.dispose();
+    }
+
+    #[test]
+    fn network_errors_are_queued() {
+        let mock = MockTransport::new().fail(HttpMethod::POST, "/todos", HttpError::Network("sem internet".to_string()));
+        setup(&mock, |queue, client| {
+            let result = block_on(client.post("/todos").json(&"a").send());
+            assert!(matches!(result, Err(HttpError::Queued)));
+            assert_eq!(queue.pending_count.get_untracked(), 1);
+            let sent = mock.last_request().unwrap();
+            let key = &queue.items.with_untracked(|items| items[0].key.clone());
+            assert_eq!(sent.headers.get(IDEMPOTENCY_HEADER), Some(key));
+        });
+    }
+
+    #[test]
+    fn pending_items_keep_order() {
+        let mock = MockTransport::new().fail(HttpMethod::POST, "/todos", HttpError::Network("sem internet".to_string()));
+        setup(&mock, |queue, client| {
+            let _ = block_on(client.post("/todos").send());
+            let second = block_on(client.delete("/todos/1").send());
+            assert!(matches!(second, Err(HttpError::Queued)));
+            assert_eq!(mock.requests().len(), 1);
+            let urls = queue.items.with_untracked(|items| items.iter().map(|i| i.url.clone()).collect::<Vec<_>>());
+            assert_eq!(urls, ["https://api.local/todos", "https://api.local/todos/1"]);
+        });
+    }
+
+    #[test]
+    fn status_errors_are_not_queued() {
+        let mock = MockTransport::new().on(HttpMethod::POST, "/todos", 503, "");
+        setup(&mock, |queue, client| {
+            let result = block_on(client.post("/todos").send());
+            assert!(matches!(result, Err(HttpError::Status { status: 503, .. })));
+            assert_eq!(queue.pending_count.get_untracked(), 0);
+        });
+    }
+
+    #[test]
+    fn gets_pass_through() {
+        let mock = MockTransport::new().fail(HttpMethod::GET, "/todos", HttpError::Network("sem internet".to_string()));
+        setup(&mock, |queue, client| {
+            let result = block_on(client.get("/todos").send());
+            assert!(matches!(result, Err(HttpError::Network(_))));
+            assert_eq!(queue.pending_count.get_untracked(), 0);
+        });
+    }
+
+    #[test]
+    fn replay_sends_in_order_with_same_key() {
+        let mock = MockTransport::new()
+            .fail(HttpMethod::POST, "/todos", HttpError::Network("sem internet".to_string()))
+            .once()
+            .on(HttpMethod::POST, "/todos", 201, "")
+            .on(HttpMethod::DELETE, "/todos/*", 204, "");
+        setup(&mock, |queue, client| {
+            let _ = block_on(client.post("/todos").send());
+            let _ = block_on(client.delete("/todos/1").send());
+            let key = queue.items.with_untracked(|items| items[0].key.clone());
+            mock.clear_requests();
+
+            block_on(queue.replay_pending(&client));
+            assert_eq!(queue.pending_count.get_untracked(), 0);
+            assert!(queue.last_sync.get_untracked().is_some());
+            assert!(!queue.replaying.get_untracked());
+            let sent = mock.requests();
+            assert_eq!(sent.len(), 2);
+            assert_eq!(sent[0].method, HttpMethod::POST);
+            assert_eq!(sent[0].headers.get(IDEMPOTENCY_HEADER), Some(&key));
+            assert_eq!(sent[1].method, HttpMethod::DELETE);
+        });
+    }
+
+    #[test]
+    fn replay_retries_network_errors_and_keeps_the_item() {
+        let mock = MockTransport::new().fail(HttpMethod::POST, "/todos", HttpError::Network("sem internet".to_string()));
+        setup(&mock, |queue, client| {
+            let _ = block_on(client.post("/todos").send());
+            take_sleeps();
+
+            block_on(queue.replay_pending(&client));
+            assert_eq!(mock.calls(HttpMethod::POST, "/todos"), 1 + MAX_REPLAY_ATTEMPTS as usize);
+            assert_eq!(take_sleeps().len(), MAX_REPLAY_ATTEMPTS as usize - 1);
+            assert_eq!(queue.pending_count.get_untracked(), 1);
+            assert!(!queue.replaying.get_untracked());
+        });
+    }
+
+    #[test]
+    fn replay_drops_client_errors() {
+        let mock = MockTransport::new()
+            .fail(HttpMethod::POST, "/todos", HttpError::Network("sem internet".to_string()))
+            .once()
+            .on(HttpMethod::POST, "/todos", 422, "inválido")
+            .on(HttpMethod::PUT, "/todos/*", 200, "");
+        setup(&mock, |queue, client| {
+            let _ = block_on(client.post("/todos").send());
+            let _ = block_on(client.put("/todos/1").send());
+
+            block_on(queue.replay_pending(&client));
+            assert_eq!(queue.pending_count.get_untracked(), 0);
+            assert_eq!(mock.calls(HttpMethod::PUT, "/todos/*"), 1);
+            assert!(take_sleeps().is_empty());
+        });
+    }
+}
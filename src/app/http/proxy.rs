@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use futures::future::{FutureExt, LocalBoxFuture};
+use serde::{Deserialize, Serialize};
+
+use wasm_bindgen_futures::JsFuture;
+
+use super::{js_message, FetchTransport, HttpError, HttpRequest, HttpResponse, HttpResult, Multipart, Part, Progress, RequestBody, StreamingResponse, Transport};
+use crate::tauri_bridge::{self, InvokeError};
+
+// --- PROXY TAURI ---
+
+// No app desktop, manda a requisição para o comando `proxy_request` do backend,
+// que a executa nativamente (sem CORS). No navegador cai no `fetch` normal:
+//
+//     let client = HttpClient::new("https://httpbin.org").with_transport(TauriProxyTransport);
+//
+// O host precisa estar no allow-list de `allow-proxy-request` em
+// `src-tauri/capabilities/default.json`. Não há streaming real nem cancelamento
+// no meio do caminho: a resposta chega inteira e é descartada se abortada.
+pub struct TauriProxyTransport;
+
+// Requisição e resposta vão como bytes pelo IPC, no mesmo formato: o tamanho de um
+// cabeçalho JSON (u32 big-endian), o JSON e depois o body cru.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProxyRequest<'a> {
+    method: &'a str,
+    url: &'a str,
+    headers: &'a HashMap<String, String>,
+    body: ProxyBody,
+    timeout_ms: Option<u32>,
+}
+
+// Num multipart, o body cru é a concatenação dos campos, na ordem de `parts`;
+// o backend remonta o formulário.
+#[derive(Serialize)]
+#[serde(tag = "kind", content = "parts")]
+enum ProxyBody {
+    Empty,
+    Raw,
+    Multipart(Vec<ProxyPart>),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProxyPart {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    len: usize,
+}
+
+#[derive(Deserialize)]
+struct ProxyResponse {
+    status: u16,
+    headers: HashMap<String, String>,
+}
+
+// Espelho do `ProxyError` do backend
+#[derive(Deserialize)]
+#[serde(tag = "kind", content = "message")]
+enum ProxyError {
+    Forbidden(String),
+    Invalid(String),
+    Timeout,
+    Network(String),
+}
+
+impl Transport for TauriProxyTransport {
+    fn send<'a>(&'a self, req: &'a HttpRequest) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        if !tauri_bridge::is_tauri() {
+            return FetchTransport.send(req);
+        }
+        proxy(req).boxed_local()
+    }
+
+    fn stream<'a>(&'a self, req: &'a HttpRequest) -> LocalBoxFuture<'a, HttpResult<StreamingResponse>> {
+        if !tauri_bridge::is_tauri() {
+            return FetchTransport.stream(req);
+        }
+        async move { proxy(req).await.map(StreamingResponse::buffered) }.boxed_local()
+    }
+}
+
+async fn proxy(req: &HttpRequest) -> HttpResult<HttpResponse> {
+    let (body, data) = match &req.body {
+        None => (ProxyBody::Empty, Vec::new()),
+        Some(RequestBody::Text(text)) => (ProxyBody::Raw, text.as_bytes().to_vec()),
+        Some(RequestBody::Multipart(multipart)) => multipart_parts(multipart).await?,
+    };
+    if req.is_aborted() {
+        return Err(HttpError::Aborted);
+    }
+
+    let head = ProxyRequest {
+        method: req.method.as_str(),
+        url: &req.url,
+        headers: &req.headers,
+        body,
+        timeout_ms: req.timeout,
+    };
+    let head = serde_json::to_vec(&head).map_err(|e| HttpError::InvalidRequest(e.to_string()))?;
+    let result = tauri_bridge::invoke_bytes("proxy_request", &frame(&head, &data)).await;
+
+    // O backend não tem como ser interrompido; só ignoramos o resultado
+    if req.is_aborted() {
+        return Err(HttpError::Aborted);
+    }
+    let raw = result.map_err(|e| invoke_error(e, req.timeout))?;
+    let (head, body) = unframe(&raw).ok_or_else(|| HttpError::Decode("resposta do proxy truncada".to_string()))?;
+    let ProxyResponse { status, headers } = serde_json::from_slice(head).map_err(|e| HttpError::Decode(e.to_string()))?;
+    let body = body.to_vec();
+
+    if let Some(progress) = &req.progress {
+        let total = body.len() as u64;
+        progress(Progress { loaded: total, total: Some(total) });
+    }
+    if !(200..300).contains(&status) {
+        let body = String::from_utf8_lossy(&body).into_owned();
+        return Err(HttpError::Status { status, headers, body });
+    }
+    Ok(HttpResponse { status, headers, body })
+}
+
+// Lê os campos (blobs inclusive) para um buffer só, descrevendo cada um em `parts`.
+// Como no `FormData`, um blob sem nome vai como "blob".
+async fn multipart_parts(multipart: &Multipart) -> HttpResult<(ProxyBody, Vec<u8>)> {
+    let mut parts = Vec::new();
+    let mut data = Vec::new();
+    for (name, part) in multipart.parts() {
+        let (filename, content_type, bytes) = match part {
+            Part::Text(value) => (None, None, value.as_bytes().to_vec()),
+            Part::Blob { blob, filename } => {
+                let buffer = JsFuture::from(blob.array_buffer())
+                    .await
+                    .map_err(|e| HttpError::InvalidRequest(js_message(&e)))?;
+                let content_type = Some(blob.type_()).filter(|t| !t.is_empty());
+                let filename = filename.clone().unwrap_or_else(|| "blob".to_string());
+                (Some(filename), content_type, js_sys::Uint8Array::new(&buffer).to_vec())
+            }
+            Part::Bytes { data, filename, content_type } => (Some(filename.clone()), content_type.clone(), data.clone()),
+        };
+        parts.push(ProxyPart { name: name.clone(), filename, content_type, len: bytes.len() });
+        data.extend(bytes);
+    }
+    Ok((ProxyBody::Multipart(parts), data))
+}
+
+fn frame(head: &[u8], body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + head.len() + body.len());
+    out.extend_from_slice(&(head.len() as u32).to_be_bytes());
+    out.extend_from_slice(head);
+    out.extend_from_slice(body);
+    out
+}
+
+fn unframe(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let len = u32::from_be_bytes(bytes.get(..4)?.try_into().ok()?) as usize;
+    let rest = &bytes[4..];
+    (rest.len() >= len).then(|| rest.split_at(len))
+}
+
+fn invoke_error(err: InvokeError, timeout_ms: Option<u32>) -> HttpError {
+    let InvokeError::Backend(message) = err else {
+        return HttpError::Decode(err.to_string());
+    };
+    match serde_json::from_str(&message) {
+        Ok(ProxyError::Forbidden(msg) | ProxyError::Invalid(msg)) => HttpError::InvalidRequest(msg),
+        Ok(ProxyError::Timeout) => HttpError::Timeout(timeout_ms.unwrap_or(0)),
+        Ok(ProxyError::Network(msg)) => HttpError::Network(msg),
+        // Ex.: comando sem permissão no capability
+        Err(_) => HttpError::Network(message),
+    }
+}
@@ -0,0 +1,174 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use serde::Deserialize;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, ReadableStream, ReadableStreamDefaultReader, ReadableStreamReadResult};
+
+use super::{js_message, Attempt, HttpError, HttpResult};
+
+// --- RESPONSE ---
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    // Nomes em minúsculas, como o `Headers` do fetch entrega
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn json<T: for<'de> Deserialize<'de>>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(&self.body)
+    }
+
+    // Bytes inválidos em UTF-8 viram `�`; para arquivos use `bytes()` ou `blob()`.
+    pub fn text(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.body)
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.body
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.body
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.header("content-type")
+    }
+
+    // Para `URL.createObjectURL`, downloads e `<img>`; herda o `Content-Type`.
+    pub fn blob(&self) -> HttpResult<Blob> {
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(self.body.as_slice()));
+        let options = BlobPropertyBag::new();
+        if let Some(content_type) = self.content_type() {
+            options.set_type(content_type);
+        }
+        Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+            .map_err(|e| HttpError::Decode(js_message(&e)))
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.status >= 200 && self.status < 300
+    }
+}
+
+// --- PROGRESSO ---
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    pub loaded: u64,
+    // Vem do `Content-Length`; ausente em respostas chunked
+    pub total: Option<u64>,
+}
+
+impl Progress {
+    // Entre 0.0 e 1.0, pronto para o `ProgressBar`.
+    pub fn fraction(&self) -> Option<f64> {
+        self.total
+            .filter(|&total| total > 0)
+            .map(|total| (self.loaded as f64 / total as f64).min(1.0))
+    }
+}
+
+// --- STREAMING ---
+
+pub(super) struct BodyReader {
+    reader: ReadableStreamDefaultReader,
+}
+
+impl BodyReader {
+    pub(super) fn new(stream: ReadableStream) -> Self {
+        Self { reader: stream.get_reader().unchecked_into() }
+    }
+
+    pub(super) async fn next_chunk(&mut self, attempt: &Attempt) -> Option<HttpResult<Vec<u8>>> {
+        let result = match JsFuture::from(self.reader.read()).await {
+            Ok(result) => result.unchecked_into::<ReadableStreamReadResult>(),
+            Err(err) => return Some(Err(attempt.error(&err))),
+        };
+        if result.get_done().unwrap_or(true) {
+            return None;
+        }
+        Some(Ok(js_sys::Uint8Array::new(&result.get_value()).to_vec()))
+    }
+}
+
+impl Drop for BodyReader {
+    // Largar a resposta no meio libera a conexão
+    fn drop(&mut self) {
+        let _ = self.reader.cancel();
+    }
+}
+
+pub(super) enum Body {
+    Stream {
+        reader: BodyReader,
+        attempt: Attempt,
+    },
+    Buffered(Option<Vec<u8>>),
+}
+
+// Resposta cujo body é lido aos poucos, sem guardar tudo na memória:
+//
+//     let mut download = client.get("/logs.zip").stream().await?;
+//     while let Some(chunk) = download.chunk().await {
+//         arquivo.extend(chunk?);
+//         progresso.set(download.progress().fraction().unwrap_or(0.0));
+//     }
+pub struct StreamingResponse {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    body: Body,
+    loaded: u64,
+    total: Option<u64>,
+}
+
+impl StreamingResponse {
+    pub(super) fn new(status: u16, headers: HashMap<String, String>, body: Body) -> Self {
+        let total = headers.get("content-length").and_then(|v| v.trim().parse().ok());
+        Self { status, headers, body, loaded: 0, total }
+    }
+
+    pub(super) fn buffered(response: HttpResponse) -> Self {
+        let total = Some(response.body.len() as u64);
+        Self { total, ..Self::new(response.status, response.headers, Body::Buffered(Some(response.body))) }
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    pub fn progress(&self) -> Progress {
+        Progress { loaded: self.loaded, total: self.total }
+    }
+
+    // `None` quando o body terminou.
+    pub async fn chunk(&mut self) -> Option<HttpResult<Vec<u8>>> {
+        let chunk = match &mut self.body {
+            Body::Stream { reader, attempt } => reader.next_chunk(attempt).await,
+            Body::Buffered(body) => body.take().map(Ok),
+        };
+        if let Some(Ok(chunk)) = &chunk {
+            self.loaded += chunk.len() as u64;
+        }
+        chunk
+    }
+
+    // Lê o restante do body, avisando o progresso a cada pedaço.
+    pub async fn collect(mut self, on_progress: impl Fn(Progress)) -> HttpResult<HttpResponse> {
+        let mut body = Vec::new();
+        on_progress(self.progress());
+        while let Some(chunk) = self.chunk().await {
+            body.extend_from_slice(&chunk?);
+            on_progress(self.progress());
+        }
+        Ok(HttpResponse { status: self.status, headers: self.headers, body })
+    }
+}
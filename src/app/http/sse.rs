@@ -0,0 +1,284 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use serde::de::DeserializeOwned;
+use sycamore::futures::spawn_local;
+use sycamore::prelude::*;
+use web_sys::console;
+
+use super::{backoff_delay, sleep, AbortHandle, HttpError, HttpMethod, HttpRequest};
+
+// --- EVENTOS ---
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SseEvent {
+    // Último `id:` visto no stream, mesmo que tenha vindo num evento anterior
+    pub id: Option<String>,
+    // `message` quando o servidor não manda `event:`
+    pub event: String,
+    pub data: String,
+}
+
+impl SseEvent {
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.data)
+    }
+}
+
+// Parser incremental de `text/event-stream`; os pedaços podem cortar linhas e
+// caracteres UTF-8 ao meio.
+#[derive(Default)]
+struct Parser {
+    line: Vec<u8>,
+    // Um `\r` fechou a linha anterior; ignora o `\n` que vier logo depois
+    after_cr: bool,
+    event: String,
+    data: String,
+    last_id: Option<String>,
+    retry_ms: Option<u32>,
+}
+
+impl Parser {
+    fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            let after_cr = std::mem::take(&mut self.after_cr);
+            match byte {
+                b'\n' if after_cr => {}
+                b'\r' | b'\n' => {
+                    self.after_cr = byte == b'\r';
+                    let line = std::mem::take(&mut self.line);
+                    events.extend(self.process(&String::from_utf8_lossy(&line)));
+                }
+                _ => self.line.push(byte),
+            }
+        }
+        events
+    }
+
+    // Uma linha em branco despacha o evento acumulado.
+    fn process(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            let event = std::mem::take(&mut self.event);
+            let mut data = std::mem::take(&mut self.data);
+            if data.is_empty() {
+                return None;
+            }
+            data.pop();
+            let event = if event.is_empty() { "message".to_string() } else { event };
+            return Some(SseEvent { id: self.last_id.clone(), event, data });
+        }
+        if line.starts_with(':') {
+            // Comentário, usado como keep-alive
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(ms) = value.parse() {
+                    self.retry_ms = Some(ms);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+// --- EVENT SOURCE ---
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SseState {
+    Connecting,
+    Open,
+    // Caiu; reconecta com `Last-Event-ID` depois do `retry` do servidor
+    Reconnecting { attempt: u32 },
+    // `close()`, escopo descartado, 204 ou erro 4xx
+    Closed,
+}
+
+const DEFAULT_RETRY_MS: u32 = 3_000;
+const MAX_RETRY_MS: u32 = 60_000;
+
+type Handler = Rc<dyn Fn(&SseEvent)>;
+
+struct Inner {
+    request: HttpRequest,
+    state: Signal<SseState>,
+    last_id: RefCell<Option<String>>,
+    retry_ms: Cell<u32>,
+    abort: AbortHandle,
+    // `None` = todos os tipos de evento
+    handlers: RefCell<Vec<(u32, Option<String>, Handler)>>,
+    next_id: Cell<u32>,
+}
+
+impl Inner {
+    fn dispatch(&self, event: &SseEvent) {
+        let handlers: Vec<Handler> = self.handlers.borrow().iter()
+            .filter(|(_, name, _)| name.as_deref().is_none_or(|name| name == event.event))
+            .map(|(_, _, handler)| handler.clone())
+            .collect();
+        for handler in handlers {
+            handler(event);
+        }
+    }
+}
+
+// Como o `EventSource` do navegador, mas sobre o `HttpRequest`: aceita headers
+// (ex.: `Authorization`), usa o transporte configurado e retoma do último evento
+// enviando `Last-Event-ID`.
+//
+//     let job = client.get("/jobs/42/events").event_source();
+//     let progresso = job.latest::<JobProgress>("progress");
+//     job.on("done", move |_: serde_json::Value| toasts.success("Exportação concluída"));
+//
+// Fecha sozinho quando o escopo reativo que o criou for descartado.
+#[derive(Clone)]
+pub struct EventSource {
+    inner: Rc<Inner>,
+}
+
+impl EventSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::from_request(HttpRequest::new(HttpMethod::GET, url))
+    }
+
+    // O timeout do pedido é ignorado: a conexão fica aberta indefinidamente.
+    pub fn from_request(mut request: HttpRequest) -> Self {
+        request.timeout = None;
+        request.retry = None;
+        let abort = AbortHandle::scoped();
+        let inner = Rc::new(Inner {
+            request: request.abort_with(&abort),
+            state: create_signal(SseState::Connecting),
+            last_id: RefCell::new(None),
+            retry_ms: Cell::new(DEFAULT_RETRY_MS),
+            abort,
+            handlers: RefCell::new(Vec::new()),
+            next_id: Cell::new(0),
+        });
+        spawn_local(run(inner.clone()));
+        Self { inner }
+    }
+
+    pub fn state(&self) -> ReadSignal<SseState> {
+        *self.inner.state
+    }
+
+    pub fn last_event_id(&self) -> Option<String> {
+        self.inner.last_id.borrow().clone()
+    }
+
+    // Eventos crus; `event = None` recebe todos os tipos. O handler sai junto com
+    // o escopo reativo atual.
+    pub fn on_event(&self, event: Option<&str>, handler: impl Fn(&SseEvent) + 'static) {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id.wrapping_add(1));
+        self.inner.handlers.borrow_mut().push((id, event.map(str::to_string), Rc::new(handler)));
+        let inner = Rc::downgrade(&self.inner);
+        on_cleanup(move || {
+            if let Some(inner) = inner.upgrade() {
+                inner.handlers.borrow_mut().retain(|(other, _, _)| *other != id);
+            }
+        });
+    }
+
+    // Decodifica o `data` como JSON; eventos que não batem com `T` são só logados.
+    pub fn on<T: DeserializeOwned>(&self, event: &str, handler: impl Fn(T) + 'static) {
+        self.on_event(Some(event), move |raw| match raw.json::<T>() {
+            Ok(value) => handler(value),
+            Err(err) => console::warn_1(&format!("SSE: evento `{}` ignorado ({}): {}", raw.event, err, raw.data).into()),
+        });
+    }
+
+    // O último valor recebido do tipo de evento, como signal do escopo atual.
+    pub fn latest<T: DeserializeOwned + 'static>(&self, event: &str) -> Signal<Option<T>> {
+        let latest = create_signal(None);
+        self.on(event, move |value: T| latest.set(Some(value)));
+        latest
+    }
+
+    pub fn close(&self) {
+        self.inner.abort.abort();
+        self.inner.state.set(SseState::Closed);
+    }
+}
+
+// Atalho para o caso comum: eventos `message` em JSON, ex.: para um `ProgressBar`.
+pub fn subscribe<T: DeserializeOwned + 'static>(url: impl Into<String>) -> Signal<Option<T>> {
+    EventSource::new(url).latest("message")
+}
+
+async fn run(inner: Rc<Inner>) {
+    let mut attempt = 0;
+    loop {
+        let mut request = inner.request.clone()
+            .header("Accept", "text/event-stream")
+            .header("Cache-Control", "no-cache");
+        if let Some(id) = inner.last_id.borrow().clone() {
+            request = request.header("Last-Event-ID", id);
+        }
+
+        let result = request.stream().await;
+        // Descartado durante a espera: os signals já não existem
+        if inner.abort.is_aborted() {
+            return;
+        }
+        match result {
+            // 204 é o jeito do servidor dizer "não reconecte"
+            Ok(response) if response.status == 204 => break,
+            Ok(mut response) => {
+                let is_event_stream = response.header("content-type")
+                    .is_some_and(|ct| ct.starts_with("text/event-stream"));
+                if !is_event_stream {
+                    console::warn_1(&format!("SSE: resposta não é text/event-stream: {}", inner.request.url()).into());
+                    break;
+                }
+                attempt = 0;
+                inner.state.set(SseState::Open);
+
+                let mut parser = Parser { last_id: inner.last_id.borrow().clone(), ..Parser::default() };
+                while let Some(Ok(chunk)) = response.chunk().await {
+                    if inner.abort.is_aborted() {
+                        return;
+                    }
+                    let events = parser.feed(&chunk);
+                    *inner.last_id.borrow_mut() = parser.last_id.clone();
+                    for event in events {
+                        inner.dispatch(&event);
+                    }
+                }
+                if let Some(ms) = parser.retry_ms {
+                    inner.retry_ms.set(ms);
+                }
+                if inner.abort.is_aborted() {
+                    return;
+                }
+            }
+            Err(HttpError::Aborted) => return,
+            // Como no navegador: erro do cliente não se resolve tentando de novo
+            Err(HttpError::Status { status, .. }) if (400..500).contains(&status) && status != 408 && status != 429 => {
+                console::warn_1(&format!("SSE: {} respondeu {}", inner.request.url(), status).into());
+                break;
+            }
+            Err(err) => console::warn_1(&format!("SSE: {}", err).into()),
+        }
+
+        attempt += 1;
+        inner.state.set(SseState::Reconnecting { attempt });
+        sleep(backoff_delay(inner.retry_ms.get(), MAX_RETRY_MS, attempt)).await;
+        if inner.abort.is_aborted() {
+            return;
+        }
+    }
+    inner.state.set(SseState::Closed);
+}
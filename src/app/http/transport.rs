@@ -0,0 +1,530 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use futures::future::{FutureExt, LocalBoxFuture};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AbortController, Headers, Request, RequestInit, RequestMode, Response};
+
+use super::{
+    js_message, js_name, sleep, AbortGuard, AbortHandle, Body, BodyReader, HttpError, HttpMethod, HttpRequest, HttpResponse, HttpResult,
+    RequestBody, StreamingResponse,
+};
+
+// --- TRANSPORTE ---
+
+// Executa uma única tentativa; retry e middlewares ficam por conta do `HttpRequest`.
+// Respostas fora de 2xx devem virar `HttpError::Status`.
+pub trait Transport {
+    fn send<'a>(&'a self, req: &'a HttpRequest) -> LocalBoxFuture<'a, HttpResult<HttpResponse>>;
+
+    // Por padrão lê a resposta inteira e entrega o body num único pedaço.
+    fn stream<'a>(&'a self, req: &'a HttpRequest) -> LocalBoxFuture<'a, HttpResult<StreamingResponse>> {
+        async move { self.send(req).await.map(StreamingResponse::buffered) }.boxed_local()
+    }
+}
+
+// Padrão: `window.fetch`, com o timeout aplicado via `AbortController`.
+pub struct FetchTransport;
+
+impl Transport for FetchTransport {
+    fn send<'a>(&'a self, req: &'a HttpRequest) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        fetch(req).boxed_local()
+    }
+
+    fn stream<'a>(&'a self, req: &'a HttpRequest) -> LocalBoxFuture<'a, HttpResult<StreamingResponse>> {
+        fetch_stream(req).boxed_local()
+    }
+}
+
+// Uma tentativa em andamento, com seu prazo e o cancelamento externo.
+// Ao sair de escopo (fim da leitura ou erro) o timer é desarmado.
+pub(super) struct Attempt {
+    timeout_ms: Option<u32>,
+    abort: Option<AbortHandle>,
+    timer: Option<i32>,
+    _guard: Option<AbortGuard>,
+}
+
+impl Attempt {
+    // Um AbortError veio de um `AbortHandle` ou, com timeout ativo, do nosso timer.
+    pub(super) fn error(&self, err: &JsValue) -> HttpError {
+        if self.abort.as_ref().is_some_and(AbortHandle::is_aborted) {
+            return HttpError::Aborted;
+        }
+        match (js_name(err).as_deref(), self.timeout_ms) {
+            (Some("AbortError"), Some(ms)) => HttpError::Timeout(ms),
+            (Some("AbortError"), None) => HttpError::Aborted,
+            _ => HttpError::Network(js_message(err)),
+        }
+    }
+}
+
+impl Drop for Attempt {
+    fn drop(&mut self) {
+        if let (Some(handle), Some(window)) = (self.timer, web_sys::window()) {
+            window.clear_timeout_with_handle(handle);
+        }
+    }
+}
+
+// Envia a requisição e espera os headers. O `Attempt` segue valendo para a leitura do body.
+async fn open(req: &HttpRequest) -> HttpResult<(Response, Attempt)> {
+    let window = web_sys::window()
+        .ok_or_else(|| HttpError::InvalidRequest("Sem acesso ao window".to_string()))?;
+
+    let opts = RequestInit::new();
+    opts.set_method(req.method.as_str());
+    opts.set_mode(RequestMode::Cors);
+
+    if let Some(body) = &req.body {
+        let body = body.to_js().map_err(|e| HttpError::InvalidRequest(js_message(&e)))?;
+        opts.set_body(&body);
+    }
+
+    let controller = if req.timeout.is_some() || req.abort.is_some() {
+        let controller = AbortController::new()
+            .map_err(|e| HttpError::InvalidRequest(js_message(&e)))?;
+        opts.set_signal(Some(&controller.signal()));
+        Some(controller)
+    } else {
+        None
+    };
+
+    let request = Request::new_with_str_and_init(&req.url, &opts)
+        .map_err(|e| HttpError::InvalidRequest(js_message(&e)))?;
+
+    let headers = request.headers();
+
+    // Adiciona headers
+    for (key, value) in &req.headers {
+        headers
+            .set(key, value)
+            .map_err(|e| HttpError::InvalidRequest(format!("header {}: {}", key, js_message(&e))))?;
+    }
+
+    let mut attempt = Attempt { timeout_ms: req.timeout, abort: req.abort.clone(), timer: None, _guard: None };
+    if let Some(controller) = controller {
+        attempt._guard = req.abort.as_ref().map(|handle| handle.attach(&controller));
+        // O timer aborta o fetch (e a leitura do body) se estourar o prazo
+        if let Some(ms) = req.timeout {
+            let abort = Closure::once_into_js(move || controller.abort());
+            let handle = window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(abort.unchecked_ref(), ms as i32)
+                .map_err(|e| HttpError::InvalidRequest(js_message(&e)))?;
+            attempt.timer = Some(handle);
+        }
+    }
+
+    // Faz o fetch
+    let resp_value = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(|err| attempt.error(&err))?;
+
+    let response: Response = resp_value
+        .dyn_into()
+        .map_err(|_| HttpError::Decode("fetch não devolveu um Response".to_string()))?;
+
+    Ok((response, attempt))
+}
+
+async fn fetch(req: &HttpRequest) -> HttpResult<HttpResponse> {
+    if let Some(progress) = &req.progress {
+        // Lê pedaço por pedaço para avisar o progresso
+        return fetch_stream(req).await?.collect(|p| progress(p)).await;
+    }
+
+    let (response, attempt) = open(req).await?;
+    let status = response.status();
+    let headers = collect_headers(&response.headers());
+    let body = read_array_buffer(&response, &attempt).await?;
+
+    if !response.ok() {
+        let body = String::from_utf8_lossy(&body).into_owned();
+        return Err(HttpError::Status { status, headers, body });
+    }
+
+    Ok(HttpResponse { status, headers, body })
+}
+
+async fn fetch_stream(req: &HttpRequest) -> HttpResult<StreamingResponse> {
+    let (response, attempt) = open(req).await?;
+    let status = response.status();
+    let headers = collect_headers(&response.headers());
+
+    if !response.ok() {
+        let body = read_array_buffer(&response, &attempt).await?;
+        let body = String::from_utf8_lossy(&body).into_owned();
+        return Err(HttpError::Status { status, headers, body });
+    }
+
+    let body = match response.body() {
+        Some(stream) => Body::Stream { reader: BodyReader::new(stream), attempt },
+        // Respostas sem corpo (204, HEAD...)
+        None => Body::Buffered(None),
+    };
+    Ok(StreamingResponse::new(status, headers, body))
+}
+
+async fn read_array_buffer(response: &Response, attempt: &Attempt) -> HttpResult<Vec<u8>> {
+    let promise = response.array_buffer().map_err(|e| attempt.error(&e))?;
+    let buffer = JsFuture::from(promise).await.map_err(|e| attempt.error(&e))?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
+
+// O `Headers` do fetch já entrega os nomes em minúsculas.
+fn collect_headers(headers: &Headers) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    if let Ok(Some(iter)) = js_sys::try_iter(headers) {
+        for entry in iter.flatten() {
+            let pair: js_sys::Array = entry.unchecked_into();
+            if let (Some(k), Some(v)) = (pair.get(0).as_string(), pair.get(1).as_string()) {
+                map.insert(k, v);
+            }
+        }
+    }
+    map
+}
+
+// --- MOCK ---
+
+// Cópia do que chegou ao transporte, para conferir nos testes.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: HttpMethod,
+    pub url: String,
+    pub headers: HashMap<String, String>,
+    pub body: Option<RequestBody>,
+}
+
+struct MockRoute {
+    method: HttpMethod,
+    pattern: String,
+    reply: HttpResult<HttpResponse>,
+    // `None` = responde sempre
+    remaining: Option<u32>,
+    latency_ms: Option<u32>,
+}
+
+#[derive(Default)]
+struct MockState {
+    routes: Vec<MockRoute>,
+    requests: Vec<RecordedRequest>,
+    latency_ms: u32,
+}
+
+// Transporte em memória para testes (`wasm-bindgen-test` ou nativos):
+//
+//     let mock = MockTransport::new()
+//         .on(HttpMethod::GET, "/users/*", 200, r#"[{"id": 1}]"#)
+//         .on(HttpMethod::POST, "/users", 503, "").once()
+//         .on_json(HttpMethod::POST, "/users", 201, &novo);
+//     let client = HttpClient::new("https://api.local").with_transport(mock.clone());
+//     ...
+//     assert_eq!(mock.calls(HttpMethod::POST, "/users"), 2);
+//
+// Padrões aceitam `*` como curinga e casam com a URL inteira ou, começando com `/`,
+// com o caminho depois do host (`/users/*` casa com `https://api.local/users/1`).
+// A primeira rota registrada que casar responde. Roda também no `cargo test` nativo,
+// onde a latência não espera de verdade (ver `sleep`).
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    state: Rc<RefCell<MockState>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn route(self, method: HttpMethod, pattern: &str, reply: HttpResult<HttpResponse>) -> Self {
+        self.state.borrow_mut().routes.push(MockRoute {
+            method,
+            pattern: pattern.to_string(),
+            reply,
+            remaining: None,
+            latency_ms: None,
+        });
+        self
+    }
+
+    // Status fora de 2xx vira `HttpError::Status`, como no fetch.
+    pub fn on(self, method: HttpMethod, pattern: &str, status: u16, body: impl Into<String>) -> Self {
+        let body = body.into();
+        let reply = if (200..300).contains(&status) {
+            Ok(HttpResponse { status, headers: HashMap::new(), body: body.into_bytes() })
+        } else {
+            Err(HttpError::Status { status, headers: HashMap::new(), body })
+        };
+        self.route(method, pattern, reply)
+    }
+
+    pub fn on_json<T: Serialize>(self, method: HttpMethod, pattern: &str, status: u16, data: &T) -> Self {
+        let body = serde_json::to_string(data).unwrap_or_default();
+        self.on(method, pattern, status, body)
+    }
+
+    pub fn fail(self, method: HttpMethod, pattern: &str, error: HttpError) -> Self {
+        self.route(method, pattern, Err(error))
+    }
+
+    // Header na resposta da última rota (inclusive nos erros de status).
+    pub fn header(self, key: &str, value: impl Into<String>) -> Self {
+        if let Some(route) = self.state.borrow_mut().routes.last_mut() {
+            match &mut route.reply {
+                Ok(HttpResponse { headers, .. }) | Err(HttpError::Status { headers, .. }) => {
+                    headers.insert(key.to_ascii_lowercase(), value.into());
+                }
+                Err(_) => {}
+            }
+        }
+        self
+    }
+
+    // Limita a última rota a `n` respostas; depois as próximas rotas assumem.
+    pub fn times(self, n: u32) -> Self {
+        if let Some(route) = self.state.borrow_mut().routes.last_mut() {
+            route.remaining = Some(n);
+        }
+        self
+    }
+
+    pub fn once(self) -> Self {
+        self.times(1)
+    }
+
+    // Latência só da última rota.
+    pub fn delay(self, ms: u32) -> Self {
+        if let Some(route) = self.state.borrow_mut().routes.last_mut() {
+            route.latency_ms = Some(ms);
+        }
+        self
+    }
+
+    // Latência padrão de todas as rotas. Acima do timeout da requisição, vira `Timeout`.
+    pub fn latency(self, ms: u32) -> Self {
+        self.state.borrow_mut().latency_ms = ms;
+        self
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.borrow().requests.clone()
+    }
+
+    pub fn last_request(&self) -> Option<RecordedRequest> {
+        self.state.borrow().requests.last().cloned()
+    }
+
+    pub fn calls(&self, method: HttpMethod, pattern: &str) -> usize {
+        self.state.borrow().requests.iter()
+            .filter(|r| r.method == method && url_matches(pattern, &r.url))
+            .count()
+    }
+
+    // Esquece as requisições gravadas; as rotas continuam.
+    pub fn clear_requests(&self) {
+        self.state.borrow_mut().requests.clear();
+    }
+
+    // Grava a requisição e escolhe a resposta, consumindo rotas limitadas.
+    fn reply(&self, req: &HttpRequest) -> (HttpResult<HttpResponse>, u32) {
+        let mut state = self.state.borrow_mut();
+        state.requests.push(RecordedRequest {
+            method: req.method.clone(),
+            url: req.url.clone(),
+            headers: req.headers.clone(),
+            body: req.body.clone(),
+        });
+
+        let default_latency = state.latency_ms;
+        let route = state.routes.iter_mut().find(|r| {
+            r.method == req.method && r.remaining != Some(0) && url_matches(&r.pattern, &req.url)
+        });
+        match route {
+            Some(route) => {
+                if let Some(n) = route.remaining.as_mut() {
+                    *n -= 1;
+                }
+                (route.reply.clone(), route.latency_ms.unwrap_or(default_latency))
+            }
+            None => (
+                Err(HttpError::Network(format!("MockTransport: nenhuma rota para {} {}", req.method.as_str(), req.url))),
+                default_latency,
+            ),
+        }
+    }
+}
+
+impl Transport for MockTransport {
+    fn send<'a>(&'a self, req: &'a HttpRequest) -> LocalBoxFuture<'a, HttpResult<HttpResponse>> {
+        let (reply, latency_ms) = self.reply(req);
+        let aborted = move || req.abort.as_ref().is_some_and(AbortHandle::is_aborted);
+        async move {
+            match req.timeout {
+                Some(timeout) if latency_ms > timeout => {
+                    sleep(timeout).await;
+                    return Err(if aborted() { HttpError::Aborted } else { HttpError::Timeout(timeout) });
+                }
+                _ if latency_ms > 0 => sleep(latency_ms).await,
+                _ => {}
+            }
+            if aborted() { Err(HttpError::Aborted) } else { reply }
+        }.boxed_local()
+    }
+}
+
+// `*` casa com qualquer trecho (inclusive vazio).
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else { return false };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else { return rest.is_empty() };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+fn url_matches(pattern: &str, url: &str) -> bool {
+    if glob_matches(pattern, url) {
+        return true;
+    }
+    // Padrão relativo: tenta casar com o caminho (e query) depois do host
+    let path = url.split_once("://")
+        .and_then(|(_, rest)| rest.find('/').map(|i| &rest[i..]))
+        .unwrap_or(url);
+    pattern.starts_with('/') && glob_matches(pattern, path)
+}
+
+// Nativos (`cargo test`); no wasm32 o `sleep` é o timer de verdade.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::app::http::{HttpClient, RetryPolicy, SLEEPS};
+
+    fn take_sleeps() -> Vec<u32> {
+        SLEEPS.with(|sleeps| sleeps.take())
+    }
+
+    #[test]
+    fn glob_matches_whole_url_or_path() {
+        assert!(url_matches("/users/*", "https://api.local/users/1"));
+        assert!(url_matches("https://api.local/*/posts", "https://api.local/users/posts"));
+        assert!(url_matches("/users*", "https://api.local/users?page=2"));
+        assert!(!url_matches("/users/*", "https://api.local/groups/1"));
+        assert!(!url_matches("users/*", "https://api.local/users/1"));
+        assert!(!url_matches("/users", "https://api.local/users/1"));
+    }
+
+    #[test]
+    fn routes_match_method_and_url() {
+        let mock = MockTransport::new()
+            .on(HttpMethod::GET, "/users/*", 200, "get")
+            .on(HttpMethod::POST, "/users", 201, "post");
+        let client = HttpClient::new("https://api.local").with_transport(mock.clone());
+
+        let get = block_on(client.get("/users/7").send()).unwrap();
+        assert_eq!(get.text(), "get");
+        let post = block_on(client.post("/users").send()).unwrap();
+        assert_eq!(post.status, 201);
+        let missing = block_on(client.delete("/users/7").send());
+        assert!(matches!(missing, Err(HttpError::Network(_))));
+    }
+
+    #[test]
+    fn once_and_times_fall_through_to_next_route() {
+        let mock = MockTransport::new()
+            .on(HttpMethod::GET, "/flaky", 500, "").times(2)
+            .on(HttpMethod::GET, "/flaky", 200, "ok")
+            .on(HttpMethod::GET, "/single", 200, "").once();
+        let client = HttpClient::new("https://api.local").with_transport(mock.clone());
+
+        for _ in 0..2 {
+            let result = block_on(client.get("/flaky").send());
+            assert!(matches!(result, Err(HttpError::Status { status: 500, .. })));
+        }
+        assert_eq!(block_on(client.get("/flaky").send()).unwrap().text(), "ok");
+        assert!(block_on(client.get("/single").send()).is_ok());
+        assert!(block_on(client.get("/single").send()).is_err());
+        assert_eq!(mock.calls(HttpMethod::GET, "/flaky"), 3);
+    }
+
+    #[test]
+    fn records_requests() {
+        let mock = MockTransport::new().on(HttpMethod::PUT, "/prefs", 200, "");
+        let client = HttpClient::new("https://api.local")
+            .with_header("X-App", "demo")
+            .with_transport(mock.clone());
+
+        block_on(client.put("/prefs").body("tema=escuro").send()).unwrap();
+        let recorded = mock.last_request().unwrap();
+        assert_eq!(recorded.method, HttpMethod::PUT);
+        assert_eq!(recorded.url, "https://api.local/prefs");
+        assert_eq!(recorded.headers.get("X-App").map(String::as_str), Some("demo"));
+        assert!(matches!(recorded.body, Some(RequestBody::Text(ref text)) if text == "tema=escuro"));
+
+        mock.clear_requests();
+        assert!(mock.requests().is_empty());
+    }
+
+    #[test]
+    fn latency_above_timeout_is_a_timeout() {
+        take_sleeps();
+        let mock = MockTransport::new()
+            .on(HttpMethod::GET, "/slow", 200, "").delay(500)
+            .on(HttpMethod::GET, "/fast", 200, "");
+        let client = HttpClient::new("https://api.local").with_timeout(100).with_transport(mock);
+
+        assert!(matches!(block_on(client.get("/slow").send()), Err(HttpError::Timeout(100))));
+        assert!(block_on(client.get("/fast").send()).is_ok());
+        assert_eq!(take_sleeps(), vec![100]);
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        take_sleeps();
+        let mock = MockTransport::new()
+            .on(HttpMethod::GET, "/status", 503, "").times(2)
+            .on(HttpMethod::GET, "/status", 200, "ok");
+        let client = HttpClient::new("https://api.local")
+            .with_retry(RetryPolicy::default().attempts(3).delay(300, 10_000))
+            .with_transport(mock.clone());
+
+        assert!(block_on(client.get("/status").send()).is_ok());
+        assert_eq!(mock.calls(HttpMethod::GET, "/status"), 3);
+        assert_eq!(take_sleeps(), vec![300, 600]);
+    }
+
+    #[test]
+    fn retry_after_overrides_backoff() {
+        take_sleeps();
+        let mock = MockTransport::new()
+            .on(HttpMethod::GET, "/limited", 429, "").header("Retry-After", "2").once()
+            .on(HttpMethod::GET, "/limited", 200, "ok");
+        let client = HttpClient::new("https://api.local")
+            .with_retry(RetryPolicy::default())
+            .with_transport(mock.clone());
+
+        assert!(block_on(client.get("/limited").send()).is_ok());
+        assert_eq!(take_sleeps(), vec![2_000]);
+    }
+
+    #[test]
+    fn post_is_not_retried_by_default() {
+        let mock = MockTransport::new().on(HttpMethod::POST, "/orders", 503, "");
+        let client = HttpClient::new("https://api.local")
+            .with_retry(RetryPolicy::default())
+            .with_transport(mock.clone());
+
+        assert!(block_on(client.post("/orders").send()).is_err());
+        assert_eq!(mock.calls(HttpMethod::POST, "/orders"), 1);
+    }
+}
@@ -0,0 +1,433 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::rc::Rc;
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
+use sycamore::futures::{create_suspense_task, spawn_local, spawn_local_scoped};
+use sycamore::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use super::http::{HttpError, HttpResult};
+
+type AnyData = Rc<dyn Any>;
+type Fetcher = Rc<dyn Fn() -> LocalBoxFuture<'static, HttpResult<AnyData>>>;
+type InFlight = Shared<LocalBoxFuture<'static, ()>>;
+
+// --- OPÇÕES ---
+
+#[derive(Clone, Copy)]
+pub struct QueryOptions {
+    // Enquanto fresca, a resposta em cache não é revalidada ao montar ou focar
+    pub stale_ms: u32,
+    pub refetch_interval_ms: Option<u32>,
+    pub refetch_on_focus: bool,
+    // Sem dados em cache, a primeira busca segura o `Suspense` mais próximo
+    pub suspense: bool,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self { stale_ms: 0, refetch_interval_ms: None, refetch_on_focus: true, suspense: true }
+    }
+}
+
+impl QueryOptions {
+    pub fn stale(mut self, ms: u32) -> Self {
+        self.stale_ms = ms;
+        self
+    }
+
+    pub fn interval(mut self, ms: u32) -> Self {
+        self.refetch_interval_ms = Some(ms);
+        self
+    }
+
+    pub fn no_focus_refetch(mut self) -> Self {
+        self.refetch_on_focus = false;
+        self
+    }
+
+    pub fn no_suspense(mut self) -> Self {
+        self.suspense = false;
+        self
+    }
+}
+
+// --- CACHE ---
+
+#[derive(Clone, Copy)]
+struct Entry {
+    data: Signal<Option<AnyData>>,
+    error: Signal<Option<HttpError>>,
+    fetching: Signal<bool>,
+    // `Date.now()` da última resposta; 0 = invalidada
+    updated_at: Signal<f64>,
+    // Quantos `use_query` montados observam a chave
+    observers: Signal<u32>,
+}
+
+struct Source {
+    fetcher: Fetcher,
+    options: QueryOptions,
+}
+
+#[derive(Default)]
+struct Registry {
+    entries: HashMap<String, Entry>,
+    // Fetchers dos `use_query` montados, por chave; o mais recente é usado nas
+    // revalidações e sai junto com o componente
+    sources: HashMap<String, Vec<(u32, Source)>>,
+    next_source_id: u32,
+    in_flight: HashMap<String, InFlight>,
+}
+
+impl Registry {
+    fn source(&self, key: &str) -> Option<&Source> {
+        self.sources.get(key)?.last().map(|(_, source)| source)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct QueryClient {
+    // Escopo dono dos sinais do cache; sobrevive aos componentes que usam as queries
+    scope: NodeHandle,
+    registry: Signal<Registry>,
+}
+
+impl QueryClient {
+    fn entry(&self, key: &str) -> Entry {
+        if let Some(entry) = self.registry.with_untracked(|r| r.entries.get(key).copied()) {
+            return entry;
+        }
+        let entry = self.scope.run_in(|| Entry {
+            data: create_signal(None),
+            error: create_signal(None),
+            fetching: create_signal(false),
+            updated_at: create_signal(0.0),
+            observers: create_signal(0),
+        });
+        self.registry.update_silent(|r| r.entries.insert(key.to_string(), entry));
+        entry
+    }
+
+    fn is_stale(&self, key: &str) -> bool {
+        let entry = self.entry(key);
+        let stale_ms = self.registry.with_untracked(|r| r.source(key).map(|s| s.options.stale_ms)).unwrap_or(0);
+        let updated_at = entry.updated_at.get_untracked();
+        updated_at == 0.0 || js_sys::Date::now() - updated_at >= stale_ms as f64
+    }
+
+    // Dispara a busca da chave, ou reaproveita a que já está em andamento.
+    fn fetch(&self, key: &str) -> Option<InFlight> {
+        if let Some(pending) = self.registry.with_untracked(|r| r.in_flight.get(key).cloned()) {
+            return Some(pending);
+        }
+        let fetcher = self.registry.with_untracked(|r| r.source(key).map(|s| s.fetcher.clone()))?;
+        let entry = self.entry(key);
+        let registry = self.registry;
+        let owned_key = key.to_string();
+
+        entry.fetching.set(true);
+        let task = async move {
+            match fetcher().await {
+                Ok(data) => {
+                    entry.data.set(Some(data));
+                    entry.error.set(None);
+                    entry.updated_at.set(js_sys::Date::now());
+                }
+                Err(e) => entry.error.set(Some(e)),
+            }
+            entry.fetching.set(false);
+            registry.update_silent(|r| r.in_flight.remove(&owned_key));
+        }.boxed_local().shared();
+
+        self.registry.update_silent(|r| r.in_flight.insert(key.to_string(), task.clone()));
+        // Roda fora do componente: desmontar no meio não deixa a chave presa em `fetching`
+        spawn_local(task.clone());
+        Some(task)
+    }
+
+    // Revalida as chaves observadas e vencidas, ex.: ao voltar o foco para a janela.
+    fn revalidate_observed(&self, on_focus: bool) {
+        let keys: Vec<String> = self.registry.with_untracked(|r| {
+            r.entries.iter()
+                .filter(|(_, entry)| entry.observers.get_untracked() > 0)
+                .filter(|(key, _)| !on_focus || r.source(key).is_some_and(|s| s.options.refetch_on_focus))
+                .map(|(key, _)| key.clone())
+                .collect()
+        });
+        for key in keys.iter().filter(|k| self.is_stale(k)) {
+            self.fetch(key);
+        }
+    }
+
+    // Marca como vencidas as chaves que começam com `prefix` e busca de novo as observadas.
+    // Ex.: `invalidate("users")` cobre `users`, `users/1` e `users?page=2`.
+    pub fn invalidate(&self, prefix: &str) {
+        let matching: Vec<(String, Entry)> = self.registry.with_untracked(|r| {
+            r.entries.iter()
+                .filter(|(key, _)| key.starts_with(prefix))
+                .map(|(key, entry)| (key.clone(), *entry))
+                .collect()
+        });
+        for (key, entry) in matching {
+            entry.updated_at.set_silent(0.0);
+            if entry.observers.get_untracked() > 0 {
+                self.fetch(&key);
+            }
+        }
+    }
+
+    // Grava direto no cache (ex.: atualização otimista depois de um POST).
+    pub fn set_data<T: 'static>(&self, key: &str, data: T) {
+        let entry = self.entry(key);
+        entry.data.set(Some(Rc::new(data)));
+        entry.error.set(None);
+        entry.updated_at.set(js_sys::Date::now());
+    }
+
+    pub fn get_data<T: 'static>(&self, key: &str) -> Option<Rc<T>> {
+        let data = self.registry.with_untracked(|r| r.entries.get(key).copied())?.data.get_clone_untracked()?;
+        data.downcast::<T>().ok()
+    }
+}
+
+pub fn create_query_client() -> QueryClient {
+    let client = QueryClient {
+        scope: use_current_scope(),
+        registry: create_signal(Registry::default()),
+    };
+    provide_context(client);
+
+    // Como no SWR: revalida ao focar a janela e ao reconectar
+    let window = window();
+    let on_focus = Closure::<dyn Fn()>::new(move || client.revalidate_observed(true));
+    let on_online = Closure::<dyn Fn()>::new(move || client.revalidate_observed(false));
+    let _ = window.add_event_listener_with_callback("focus", on_focus.as_ref().unchecked_ref());
+    let _ = window.add_event_listener_with_callback("online", on_online.as_ref().unchecked_ref());
+    on_cleanup(move || {
+        let _ = window.remove_event_listener_with_callback("focus", on_focus.as_ref().unchecked_ref());
+        let _ = window.remove_event_listener_with_callback("online", on_online.as_ref().unchecked_ref());
+    });
+
+    client
+}
+
+pub fn use_query_client() -> QueryClient {
+    use_context::<QueryClient>()
+}
+
+#[derive(Props)]
+pub struct QueryProviderProps {
+    pub children: Children,
+}
+
+#[component]
+pub fn QueryProvider(props: QueryProviderProps) -> View {
+    try_use_context::<QueryClient>().unwrap_or_else(create_query_client);
+    props.children.call()
+}
+
+// --- QUERY ---
+
+pub struct Query<T: 'static> {
+    // `None` até a primeira resposta; durante revalidações mantém o valor anterior
+    pub data: ReadSignal<Option<Rc<T>>>,
+    pub error: ReadSignal<Option<HttpError>>,
+    // Primeira carga, sem nada para mostrar ainda (bom para o `Spinner`)
+    pub loading: ReadSignal<bool>,
+    // Qualquer busca em andamento, inclusive revalidações em segundo plano
+    pub fetching: ReadSignal<bool>,
+    key: Signal<String>,
+    client: QueryClient,
+}
+
+// Manual: o derive exigiria `T: Copy`
+impl<T> Clone for Query<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Query<T> {}
+
+impl<T: 'static> Query<T> {
+    pub fn refetch(&self) {
+        self.client.fetch(&self.key.get_clone_untracked());
+    }
+
+    pub fn invalidate(&self) {
+        self.client.invalidate(&self.key.get_clone_untracked());
+    }
+
+    // Troca o valor em cache sem ir ao servidor.
+    pub fn set(&self, data: T) {
+        self.client.set_data(&self.key.get_clone_untracked(), data);
+    }
+}
+
+pub fn use_query<T, F, Fut>(key: impl Into<String>, fetcher: F) -> Query<T>
+where
+    T: 'static,
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = HttpResult<T>> + 'static,
+{
+    use_query_with(key, QueryOptions::default(), fetcher)
+}
+
+// Busca com cache por chave (stale-while-revalidate):
+//
+//     let ip = use_query("conexao/ip", || http::get_json::<Origem>("https://httpbin.org/ip"));
+//     view! {
+//         Suspense(fallback = || view! { Spinner() }) {
+//             (ip.data.get_clone().map(|o| o.origin.clone()).unwrap_or_default())
+//         }
+//     }
+//
+// Componentes com a mesma chave compartilham a resposta e a busca em andamento.
+// A chave deve corresponder sempre ao mesmo `T`; com tipos diferentes, `data` fica `None`.
+pub fn use_query_with<T, F, Fut>(key: impl Into<String>, options: QueryOptions, fetcher: F) -> Query<T>
+where
+    T: 'static,
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = HttpResult<T>> + 'static,
+{
+    let client = use_query_client();
+    let key: String = key.into();
+    let fetcher: Fetcher = Rc::new(move || {
+        let request = fetcher();
+        async move { request.await.map(|data| Rc::new(data) as AnyData) }.boxed_local()
+    });
+    let source_id = client.registry.update_silent(|r| {
+        let id = r.next_source_id;
+        r.next_source_id = id.wrapping_add(1);
+        r.sources.entry(key.clone()).or_default().push((id, Source { fetcher, options }));
+        id
+    });
+
+    let entry = client.entry(&key);
+    entry.observers.set_silent(entry.observers.get_untracked() + 1);
+    let cleanup_key = key.clone();
+    on_cleanup(move || {
+        entry.observers.set_silent(entry.observers.get_untracked().saturating_sub(1));
+        // O fetcher pode capturar sinais do componente desmontado
+        client.registry.update_silent(|r| {
+            if let Some(sources) = r.sources.get_mut(&cleanup_key) {
+                sources.retain(|(id, _)| *id != source_id);
+                if sources.is_empty() {
+                    r.sources.remove(&cleanup_key);
+                }
+            }
+        });
+    });
+
+    if client.is_stale(&key) {
+        if let Some(task) = client.fetch(&key) {
+            if options.suspense && entry.data.with_untracked(Option::is_none) {
+                create_suspense_task(task);
+            }
+        }
+    }
+
+    if let Some(ms) = options.refetch_interval_ms {
+        let interval_key = key.clone();
+        let tick = Closure::<dyn Fn()>::new(move || {
+            client.fetch(&interval_key);
+        });
+        let handle = window()
+            .set_interval_with_callback_and_timeout_and_arguments_0(tick.as_ref().unchecked_ref(), ms as i32)
+            .ok();
+        on_cleanup(move || {
+            if let Some(handle) = handle {
+                window().clear_interval_with_handle(handle);
+            }
+            drop(tick);
+        });
+    }
+
+    Query {
+        data: create_memo(move || entry.data.get_clone().and_then(|data| data.downcast::<T>().ok())),
+        error: *entry.error,
+        loading: create_memo(move || entry.fetching.get() && entry.data.with(Option::is_none)),
+        fetching: *entry.fetching,
+        key: create_signal(key),
+        client,
+    }
+}
+
+// --- MUTAÇÕES ---
+
+pub struct Mutation<A: 'static, R: 'static> {
+    pub loading: Signal<bool>,
+    pub error: Signal<Option<HttpError>>,
+    pub data: Signal<Option<Rc<R>>>,
+    mutator: Rc<dyn Fn(A) -> LocalBoxFuture<'static, HttpResult<R>>>,
+    invalidates: Vec<String>,
+    client: QueryClient,
+}
+
+impl<A, R> Clone for Mutation<A, R> {
+    fn clone(&self) -> Self {
+        Self {
+            loading: self.loading,
+            error: self.error,
+            data: self.data,
+            mutator: self.mutator.clone(),
+            invalidates: self.invalidates.clone(),
+            client: self.client,
+        }
+    }
+}
+
+impl<A: 'static, R: 'static> Mutation<A, R> {
+    // Prefixos de chaves revalidados quando a mutação dá certo.
+    pub fn invalidates(mut self, prefix: impl Into<String>) -> Self {
+        self.invalidates.push(prefix.into());
+        self
+    }
+
+    pub async fn run(&self, args: A) -> HttpResult<Rc<R>> {
+        self.loading.set(true);
+        let result = (self.mutator)(args).await;
+        self.loading.set(false);
+        match result {
+            Ok(data) => {
+                let data = Rc::new(data);
+                self.data.set(Some(data.clone()));
+                self.error.set(None);
+                for prefix in &self.invalidates {
+                    self.client.invalidate(prefix);
+                }
+                Ok(data)
+            }
+            Err(e) => {
+                self.error.set(Some(e.clone()));
+                Err(e)
+            }
+        }
+    }
+
+    // Dispara sem esperar; o resultado fica em `data`/`error`.
+    pub fn mutate(&self, args: A) {
+        let mutation = self.clone();
+        spawn_local_scoped(async move {
+            let _ = mutation.run(args).await;
+        });
+    }
+}
+
+pub fn use_mutation<A, R, F, Fut>(mutator: F) -> Mutation<A, R>
+where
+    F: Fn(A) -> Fut + 'static,
+    Fut: Future<Output = HttpResult<R>> + 'static,
+{
+    Mutation {
+        loading: create_signal(false),
+        error: create_signal(None),
+        data: create_signal(None),
+        mutator: Rc::new(move |args| mutator(args).boxed_local()),
+        invalidates: Vec::new(),
+        client: use_query_client(),
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::str::FromStr;
+use sycamore::prelude::*;
+use sycamore::web::events::MouseEvent;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+// --- TIPOS ---
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum RouteMode {
+    // `#/caminho` — funciona com `trunk serve` e dentro do Tauri sem configuração extra.
+    #[default]
+    Hash,
+    // `/caminho` via History API — exige que o servidor devolva o index.html.
+    History,
+}
+
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct Params {
+    values: HashMap<String, String>,
+}
+
+impl Params {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(|v| v.as_str())
+    }
+
+    pub fn parse<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.get(key).and_then(|v| v.parse().ok())
+    }
+
+    fn insert(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    fn extend(&mut self, other: &Params) {
+        for (k, v) in &other.values {
+            self.values.insert(k.clone(), v.clone());
+        }
+    }
+}
+
+// --- ROTAS ---
+
+#[derive(Clone)]
+pub struct Route {
+    pub path: &'static str,
+    pub view: Rc<dyn Fn(Params) -> View>,
+    pub children: Vec<Route>,
+}
+
+impl Route {
+    pub fn new<F>(path: &'static str, renderer: F) -> Self
+    where F: Fn(Params) -> View + 'static {
+        Self { path, view: Rc::new(renderer), children: Vec::new() }
+    }
+
+    pub fn child(mut self, route: Route) -> Self {
+        self.children.push(route);
+        self
+    }
+}
+
+#[derive(Clone)]
+struct Matched {
+    view: Rc<dyn Fn(Params) -> View>,
+    params: Params,
+}
+
+fn segments(path: &str) -> Vec<&str> {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    path.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+// Casa `pattern` com o início de `path`. Devolve os parâmetros e quantos
+// segmentos foram consumidos. `:nome` captura um segmento e `*` o restante.
+fn match_prefix(pattern: &str, path: &[&str]) -> Option<(Params, usize)> {
+    let mut params = Params::default();
+    let mut consumed = 0;
+
+    for seg in segments(pattern) {
+        if seg == "*" {
+            params.insert("*", &path[consumed..].join("/"));
+            return Some((params, path.len()));
+        }
+        let current = path.get(consumed)?;
+        if let Some(name) = seg.strip_prefix(':') {
+            params.insert(name, current);
+        } else if seg != *current {
+            return None;
+        }
+        consumed += 1;
+    }
+
+    Some((params, consumed))
+}
+
+// Casa `pattern` com o caminho inteiro, útil fora do componente `Router`.
+pub fn match_path(pattern: &str, path: &str) -> Option<Params> {
+    let segs = segments(path);
+    match match_prefix(pattern, &segs) {
+        Some((params, consumed)) if consumed == segs.len() => Some(params),
+        _ => None,
+    }
+}
+
+fn resolve(routes: &[Route], path: &[&str], inherited: &Params) -> Option<Vec<Matched>> {
+    for route in routes {
+        let Some((own, consumed)) = match_prefix(route.path, path) else { continue };
+        let mut params = inherited.clone();
+        params.extend(&own);
+        let rest = &path[consumed..];
+
+        if let Some(mut chain) = resolve(&route.children, rest, &params) {
+            chain.insert(0, Matched { view: route.view.clone(), params });
+            return Some(chain);
+        }
+        if rest.is_empty() {
+            return Some(vec![Matched { view: route.view.clone(), params }]);
+        }
+    }
+    None
+}
+
+// --- ESTADO DO ROTEADOR ---
+
+#[derive(Clone, Copy)]
+pub struct RouterHandle {
+    pub mode: RouteMode,
+    path: Signal<String>,
+}
+
+impl RouterHandle {
+    pub fn path(&self) -> String {
+        self.path.get_clone()
+    }
+
+    pub fn params(&self, pattern: &str) -> Option<Params> {
+        match_path(pattern, &self.path.get_clone())
+    }
+
+    pub fn navigate(&self, to: &str) {
+        self.go(to, false);
+    }
+
+    pub fn replace(&self, to: &str) {
+        self.go(to, true);
+    }
+
+    pub fn back(&self) {
+        if let Ok(history) = window().history() {
+            let _ = history.back();
+        }
+    }
+
+    pub fn href(&self, to: &str) -> String {
+        match self.mode {
+            RouteMode::Hash => format!("#{}", to),
+            RouteMode::History => to.to_string(),
+        }
+    }
+
+    fn go(&self, to: &str, replace: bool) {
+        if self.path.with_untracked(|p| p == to) {
+            return;
+        }
+        let href = self.href(to);
+        if let Ok(history) = window().history() {
+            let _ = if replace {
+                history.replace_state_with_url(&JsValue::NULL, "", Some(&href))
+            } else {
+                history.push_state_with_url(&JsValue::NULL, "", Some(&href))
+            };
+        }
+        // pushState/replaceState não disparam hashchange nem popstate
+        self.path.set(to.to_string());
+    }
+}
+
+fn current_path(mode: RouteMode) -> String {
+    let location = window().location();
+    let raw = match mode {
+        RouteMode::Hash => location.hash().unwrap_or_default().trim_start_matches('#').to_string(),
+        RouteMode::History => location.pathname().unwrap_or_default(),
+    };
+    if raw.is_empty() { "/".to_string() } else { raw }
+}
+
+// Cria o roteador no escopo atual e o disponibiliza via contexto.
+pub fn create_router(mode: RouteMode) -> RouterHandle {
+    let router = RouterHandle { mode, path: create_signal(current_path(mode)) };
+
+    let event = match mode {
+        RouteMode::Hash => "hashchange",
+        RouteMode::History => "popstate",
+    };
+    let listener = Closure::<dyn Fn()>::new(move || router.path.set(current_path(mode)));
+    let _ = window().add_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+    on_cleanup(move || {
+        let _ = window().remove_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+    });
+
+    provide_context(router);
+    router
+}
+
+pub fn use_router() -> RouterHandle {
+    use_context::<RouterHandle>()
+}
+
+// --- COMPONENTES ---
+
+#[derive(Clone)]
+struct OutletContext {
+    chain: Rc<Vec<Matched>>,
+    depth: usize,
+}
+
+fn render_at(chain: Rc<Vec<Matched>>, depth: usize) -> View {
+    let Some(matched) = chain.get(depth).cloned() else { return view! {} };
+    provide_context_in_new_scope(OutletContext { chain, depth: depth + 1 }, move || {
+        (matched.view)(matched.params)
+    })
+}
+
+#[derive(Props)]
+pub struct RouterProps {
+    pub routes: Vec<Route>,
+    #[prop(default)]
+    pub mode: RouteMode,
+    #[prop(default)]
+    pub fallback: Option<Rc<dyn Fn() -> View>>,
+}
+
+#[component]
+pub fn Router(props: RouterProps) -> View {
+    let router = try_use_context::<RouterHandle>().unwrap_or_else(|| create_router(props.mode));
+    let routes = props.routes;
+    let fallback = props.fallback;
+
+    view! {
+        (move || {
+            let path = router.path.get_clone();
+            match resolve(&routes, &segments(&path), &Params::default()) {
+                Some(chain) => render_at(Rc::new(chain), 0),
+                None => match &fallback {
+                    Some(fallback) => fallback(),
+                    None => view! { NotFound(path = path) },
+                },
+            }
+        })
+    }
+}
+
+// Renderiza a rota filha dentro do layout da rota pai.
+#[component]
+pub fn Outlet() -> View {
+    match try_use_context::<OutletContext>() {
+        Some(ctx) => render_at(ctx.chain, ctx.depth),
+        None => view! {},
+    }
+}
+
+#[derive(Props)]
+pub struct NotFoundProps {
+    pub path: String,
+}
+
+#[component]
+pub fn NotFound(props: NotFoundProps) -> View {
+    view! {
+        div(class="column", style="gap: 8px; align-items: center; padding: 32px;") {
+            span(class="ui-label header") { "404" }
+            span(class="ui-label caption") { (format!("Página não encontrada: {}", props.path)) }
+        }
+    }
+}
+
+#[derive(Props)]
+pub struct LinkProps {
+    pub to: &'static str,
+    pub children: Children,
+    #[prop(default)]
+    pub class: &'static str,
+}
+
+#[component]
+pub fn Link(props: LinkProps) -> View {
+    let router = use_router();
+    let to = props.to;
+    let children = props.children.call();
+    let is_active = move || router.path.with(|p| p == to);
+
+    let on_click = move |e: MouseEvent| {
+        if e.ctrl_key() || e.meta_key() || e.shift_key() || e.button() != 0 {
+            return;
+        }
+        e.prevent_default();
+        router.navigate(to);
+    };
+
+    view! {
+        a(
+            class=move || format!("link {} {}", props.class, if is_active() { "active" } else { "" }),
+            href=router.href(to),
+            on:click=on_click
+        ) { (children) }
+    }
+}
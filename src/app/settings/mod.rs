@@ -0,0 +1,157 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+use crate::tauri_bridge::{self, InvokeError};
+
+// --- ERROS ---
+
+#[derive(Debug, Clone)]
+pub enum SettingsError {
+    Storage(String),
+    Parse(String),
+    // Arquivo gravado por uma versão mais nova do app
+    UnsupportedVersion(u32),
+}
+
+impl std::fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingsError::Storage(msg) => write!(f, "Falha ao acessar configurações: {}", msg),
+            SettingsError::Parse(msg) => write!(f, "Configurações inválidas: {}", msg),
+            SettingsError::UnsupportedVersion(v) => write!(f, "Versão de configurações não suportada: {}", v),
+        }
+    }
+}
+
+impl From<InvokeError> for SettingsError {
+    fn from(err: InvokeError) -> Self {
+        SettingsError::Storage(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for SettingsError {
+    fn from(err: serde_json::Error) -> Self {
+        SettingsError::Parse(err.to_string())
+    }
+}
+
+pub type SettingsResult<T> = Result<T, SettingsError>;
+
+// --- ARMAZENAMENTO ---
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    version: u32,
+    data: Value,
+}
+
+#[derive(Serialize)]
+struct LoadArgs<'a> {
+    key: &'a str,
+}
+
+#[derive(Serialize)]
+struct SaveArgs<'a> {
+    key: &'a str,
+    contents: &'a str,
+}
+
+fn js_error(err: JsValue) -> SettingsError {
+    SettingsError::Storage(err.as_string().unwrap_or_else(|| format!("{:?}", err)))
+}
+
+fn local_storage() -> SettingsResult<web_sys::Storage> {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .ok_or_else(|| SettingsError::Storage("localStorage indisponível".to_string()))
+}
+
+async fn read_raw(key: &str) -> SettingsResult<Option<String>> {
+    if tauri_bridge::is_tauri() {
+        Ok(tauri_bridge::invoke("load_settings", &LoadArgs { key }).await?)
+    } else {
+        local_storage()?
+            .get_item(&format!("settings:{}", key))
+            .map_err(js_error)
+    }
+}
+
+async fn write_raw(key: &str, contents: &str) -> SettingsResult<()> {
+    if tauri_bridge::is_tauri() {
+        Ok(tauri_bridge::invoke("save_settings", &SaveArgs { key, contents }).await?)
+    } else {
+        local_storage()?
+            .set_item(&format!("settings:{}", key), contents)
+            .map_err(js_error)
+    }
+}
+
+// --- STORE ---
+
+type Migration = Rc<dyn Fn(Value) -> Value>;
+
+pub struct SettingsStore<T> {
+    key: &'static str,
+    version: u32,
+    // migrations[v] leva os dados da versão v para v + 1
+    migrations: BTreeMap<u32, Migration>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Clone for SettingsStore<T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key,
+            version: self.version,
+            migrations: self.migrations.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + Default> SettingsStore<T> {
+    // `key` vira o nome do arquivo (Tauri) ou a chave no localStorage.
+    pub fn new(key: &'static str, version: u32) -> Self {
+        Self { key, version, migrations: BTreeMap::new(), _marker: PhantomData }
+    }
+
+    pub fn migration<F>(mut self, from: u32, migrate: F) -> Self
+    where F: Fn(Value) -> Value + 'static {
+        self.migrations.insert(from, Rc::new(migrate));
+        self
+    }
+
+    fn migrate(&self, mut envelope: Envelope) -> SettingsResult<Value> {
+        if envelope.version > self.version {
+            return Err(SettingsError::UnsupportedVersion(envelope.version));
+        }
+        while envelope.version < self.version {
+            // Versões sem migração registrada só ganharam campos com default
+            if let Some(migrate) = self.migrations.get(&envelope.version) {
+                envelope.data = migrate(envelope.data);
+            }
+            envelope.version += 1;
+        }
+        Ok(envelope.data)
+    }
+
+    // Sem nada salvo devolve `T::default()`.
+    pub async fn load(&self) -> SettingsResult<T> {
+        let Some(raw) = read_raw(self.key).await? else {
+            return Ok(T::default());
+        };
+        let envelope: Envelope = serde_json::from_str(&raw)?;
+        let data = self.migrate(envelope)?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    pub async fn save(&self, value: &T) -> SettingsResult<()> {
+        let envelope = Envelope { version: self.version, data: serde_json::to_value(value)? };
+        let contents = serde_json::to_string_pretty(&envelope)?;
+        write_raw(self.key, &contents).await
+    }
+}
@@ -0,0 +1,253 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+use sycamore::prelude::*;
+use sycamore::web::events::MouseEvent;
+
+// --- COLUNAS ---
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SortDirection { Asc, Desc }
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum SelectionMode { #[default] None, Single, Multiple }
+
+type Comparator<T> = Rc<dyn Fn(&T, &T) -> Ordering>;
+
+pub struct TableColumn<T> {
+    pub header: &'static str,
+    pub accessor: Rc<dyn Fn(&T) -> String>,
+    pub width: Option<&'static str>,
+    pub sortable: bool,
+    pub compare: Option<Comparator<T>>,
+}
+
+impl<T> Clone for TableColumn<T> {
+    fn clone(&self) -> Self {
+        Self {
+            header: self.header,
+            accessor: self.accessor.clone(),
+            width: self.width,
+            sortable: self.sortable,
+            compare: self.compare.clone(),
+        }
+    }
+}
+
+impl<T> TableColumn<T> {
+    pub fn new<F>(header: &'static str, accessor: F) -> Self
+    where F: Fn(&T) -> String + 'static {
+        Self { header, accessor: Rc::new(accessor), width: None, sortable: false, compare: None }
+    }
+
+    // Aceita qualquer valor CSS: "120px", "20%", "auto"...
+    pub fn width(mut self, width: &'static str) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn sortable(mut self) -> Self {
+        self.sortable = true;
+        self
+    }
+
+    // Sem comparador próprio a coluna ordena pelo texto do accessor.
+    pub fn sort_by<F>(mut self, compare: F) -> Self
+    where F: Fn(&T, &T) -> Ordering + 'static {
+        self.sortable = true;
+        self.compare = Some(Rc::new(compare));
+        self
+    }
+
+    fn cmp(&self, a: &T, b: &T) -> Ordering {
+        match &self.compare {
+            Some(compare) => compare(a, b),
+            None => (self.accessor)(a).cmp(&(self.accessor)(b)),
+        }
+    }
+}
+
+// --- TABELA ---
+
+#[derive(Props)]
+pub struct DataTableProps<T: 'static> {
+    pub columns: Vec<TableColumn<T>>,
+    pub rows: Signal<Vec<T>>,
+    #[prop(default = 10)]
+    pub page_size: usize,
+    #[prop(default)]
+    pub selection: SelectionMode,
+    // Índices em `rows` (ordem original, não a ordenada).
+    #[prop(default)]
+    pub selected: Signal<Vec<usize>>,
+    #[prop(default)]
+    pub empty: Option<Rc<dyn Fn() -> View>>,
+}
+
+#[component]
+pub fn DataTable<T: 'static>(props: DataTableProps<T>) -> View {
+    let columns = Rc::new(props.columns);
+    let rows = props.rows;
+    let selected = props.selected;
+    let selection = props.selection;
+    let page_size = props.page_size.max(1);
+    let empty = props.empty;
+
+    let sort = create_signal(Vec::<(usize, SortDirection)>::new());
+    let page = create_signal(0usize);
+
+    let order = create_memo({
+        let columns = columns.clone();
+        move || {
+            let keys = sort.get_clone();
+            rows.with(|rows| {
+                let mut idx: Vec<usize> = (0..rows.len()).collect();
+                idx.sort_by(|&a, &b| {
+                    keys.iter()
+                        .map(|&(col, dir)| {
+                            let ord = columns[col].cmp(&rows[a], &rows[b]);
+                            if dir == SortDirection::Desc { ord.reverse() } else { ord }
+                        })
+                        .find(|ord| *ord != Ordering::Equal)
+                        .unwrap_or(Ordering::Equal)
+                });
+                idx
+            })
+        }
+    });
+
+    let page_count = create_memo(move || order.with(|o| o.len().div_ceil(page_size)).max(1));
+
+    // Dados novos podem deixar a página atual fora do intervalo
+    create_effect(move || {
+        let last = page_count.get() - 1;
+        if page.get_untracked() > last {
+            page.set(last);
+        }
+    });
+
+    // Shift+clique acrescenta a coluna à ordenação em vez de substituí-la
+    let toggle_sort = move |col: usize, additive: bool| {
+        sort.update(|keys| {
+            let current = keys.iter().position(|&(c, _)| c == col);
+            match (current, additive) {
+                (Some(pos), _) if keys[pos].1 == SortDirection::Asc => keys[pos].1 = SortDirection::Desc,
+                (Some(pos), true) => { keys.remove(pos); }
+                (Some(_), false) => keys.clear(),
+                (None, true) => keys.push((col, SortDirection::Asc)),
+                (None, false) => *keys = vec![(col, SortDirection::Asc)],
+            }
+        });
+    };
+
+    let toggle_row = move |row: usize| {
+        selected.update(|sel| match selection {
+            SelectionMode::None => {}
+            SelectionMode::Single => {
+                *sel = if sel.as_slice() == [row] { Vec::new() } else { vec![row] };
+            }
+            SelectionMode::Multiple => {
+                if let Some(pos) = sel.iter().position(|&r| r == row) {
+                    sel.remove(pos);
+                } else {
+                    sel.push(row);
+                }
+            }
+        });
+    };
+
+    let headers = columns.iter().enumerate().map(|(col, column)| {
+        let header = column.header;
+        let sortable = column.sortable;
+        let style = column.width.map(|w| format!("width: {};", w)).unwrap_or_default();
+        let indicator = move || {
+            sort.with(|keys| {
+                let Some(pos) = keys.iter().position(|&(c, _)| c == col) else { return String::new() };
+                let arrow = if keys[pos].1 == SortDirection::Asc { "▲" } else { "▼" };
+                if keys.len() > 1 { format!("{}{}", arrow, pos + 1) } else { arrow.to_string() }
+            })
+        };
+        view! {
+            th(
+                class=if sortable { "sortable" } else { "" },
+                style=style,
+                on:click=move |e: MouseEvent| if sortable { toggle_sort(col, e.shift_key()) }
+            ) {
+                (header)
+                span(class="sort-indicator") { (indicator) }
+            }
+        }
+    }).collect::<Vec<_>>();
+
+    let body = {
+        let columns = columns.clone();
+        move || {
+            let start = page.get() * page_size;
+            let visible: Vec<usize> = order.with(|o| o.iter().skip(start).take(page_size).copied().collect());
+
+            if visible.is_empty() {
+                let content = match &empty {
+                    Some(empty) => empty(),
+                    None => view! { span(class="ui-label caption") { "Nenhum registro encontrado" } },
+                };
+                let colspan = columns.len().to_string();
+                return view! {
+                    tr(class="data-table-empty") {
+                        td(colspan=colspan) { (content) }
+                    }
+                };
+            }
+
+            visible.into_iter().map(|row| {
+                let cells = rows.with(|rows| {
+                    columns.iter()
+                        .map(|c| (c.accessor)(&rows[row]))
+                        .map(|cell| view! { td { (cell) } })
+                        .collect::<Vec<_>>()
+                });
+                let is_selected = move || selected.with(|s| s.contains(&row));
+                view! {
+                    tr(
+                        class=move || if is_selected() { "selected" } else { "" },
+                        on:click=move |_| toggle_row(row)
+                    ) {
+                        (cells)
+                    }
+                }
+            }).collect::<Vec<_>>().into()
+        }
+    };
+
+    let summary = move || {
+        let total = rows.with(|r| r.len());
+        let start = (page.get() * page_size).min(total);
+        let end = (start + page_size).min(total);
+        format!("{}–{} de {}", if total == 0 { 0 } else { start + 1 }, end, total)
+    };
+
+    view! {
+        div(class="data-table-wrapper") {
+            table(class=if selection == SelectionMode::None { "data-table" } else { "data-table selectable" }) {
+                thead { tr { (headers) } }
+                tbody { (body) }
+            }
+            div(class="row data-table-footer") {
+                span(class="ui-label caption") { (summary) }
+                div(class="row", style="gap: 4px;") {
+                    button(
+                        class="btn btn-ghost",
+                        disabled=move || page.get() == 0,
+                        on:click=move |_| page.set(page.get().saturating_sub(1))
+                    ) { "‹" }
+                    span(class="ui-label caption") {
+                        (move || format!("{} / {}", page.get() + 1, page_count.get()))
+                    }
+                    button(
+                        class="btn btn-ghost",
+                        disabled=move || page.get() + 1 >= page_count.get(),
+                        on:click=move |_| page.set(page.get() + 1)
+                    ) { "›" }
+                }
+            }
+        }
+    }
+}
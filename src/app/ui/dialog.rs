@@ -0,0 +1,403 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use futures::channel::oneshot;
+use sycamore::prelude::*;
+use sycamore::web::events::{Event, KeyboardEvent, MouseEvent};
+use wasm_bindgen::JsCast;
+use web_sys::{console, HtmlElement, HtmlInputElement};
+
+const FOCUSABLE: &str =
+    "button:not([disabled]), [href], input:not([disabled]), select, textarea, [tabindex]:not([tabindex='-1'])";
+
+// --- PILHA DE DIÁLOGOS ---
+
+#[derive(Clone)]
+enum DialogRequest {
+    Confirm { message: String, destructive: bool },
+    Prompt { message: String, initial: String },
+}
+
+#[derive(Clone)]
+struct PendingDialog {
+    id: u32,
+    request: DialogRequest,
+    respond: Rc<RefCell<Option<oneshot::Sender<Option<String>>>>>,
+}
+
+impl PartialEq for PendingDialog {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Dialogs {
+    // Ids dos diálogos abertos, do mais antigo para o do topo
+    stack: Signal<Vec<u32>>,
+    pending: Signal<Vec<PendingDialog>>,
+    next_id: Signal<u32>,
+    // `DialogProvider`s montados; sem nenhum, não há quem mostre `confirm`/`prompt`
+    providers: Signal<u32>,
+}
+
+impl Dialogs {
+    fn next_id(&self) -> u32 {
+        let id = self.next_id.get_untracked();
+        self.next_id.set(id + 1);
+        id
+    }
+
+    fn open_layer(&self, id: u32) {
+        self.stack.update(|s| if !s.contains(&id) { s.push(id) });
+    }
+
+    fn close_layer(&self, id: u32) {
+        if self.stack.is_alive() {
+            self.stack.update(|s| s.retain(|l| *l != id));
+        }
+    }
+
+    // Sem `DialogProvider` responde na hora como cancelado, em vez de esperar para sempre.
+    async fn request(&self, request: DialogRequest) -> Option<String> {
+        if self.providers.get_untracked() == 0 {
+            console::warn_1(&"Dialogs: confirm/prompt sem um DialogProvider montado".into());
+            return None;
+        }
+        let (tx, rx) = oneshot::channel();
+        let id = self.next_id();
+        self.pending.update(|p| p.push(PendingDialog {
+            id,
+            request,
+            respond: Rc::new(RefCell::new(Some(tx))),
+        }));
+        rx.await.ok().flatten()
+    }
+
+    pub async fn confirm(&self, message: impl Into<String>) -> bool {
+        let request = DialogRequest::Confirm { message: message.into(), destructive: false };
+        self.request(request).await.is_some()
+    }
+
+    // Mesmo que `confirm`, mas com o botão de confirmação em vermelho.
+    pub async fn confirm_destructive(&self, message: impl Into<String>) -> bool {
+        let request = DialogRequest::Confirm { message: message.into(), destructive: true };
+        self.request(request).await.is_some()
+    }
+
+    pub async fn prompt(&self, message: impl Into<String>, initial: impl Into<String>) -> Option<String> {
+        let request = DialogRequest::Prompt { message: message.into(), initial: initial.into() };
+        self.request(request).await
+    }
+}
+
+pub fn create_dialogs() -> Dialogs {
+    let dialogs = Dialogs {
+        stack: create_signal(Vec::new()),
+        pending: create_signal(Vec::new()),
+        next_id: create_signal(1),
+        providers: create_signal(0),
+    };
+    provide_context(dialogs);
+    dialogs
+}
+
+pub fn use_dialogs() -> Dialogs {
+    use_context::<Dialogs>()
+}
+
+fn focusables(panel: &HtmlElement) -> Vec<HtmlElement> {
+    let Ok(nodes) = panel.query_selector_all(FOCUSABLE) else { return Vec::new() };
+    (0..nodes.length())
+        .filter_map(|i| nodes.item(i))
+        .filter_map(|n| n.dyn_into::<HtmlElement>().ok())
+        .collect()
+}
+
+// --- COMPONENTES ---
+
+#[derive(Props)]
+pub struct DialogProps {
+    pub open: Signal<bool>,
+    pub children: Children,
+    #[prop(default)]
+    pub title: &'static str,
+    // Fecha com Escape e clique no fundo
+    #[prop(default = true)]
+    pub dismissible: bool,
+}
+
+// Modal com foco preso e empilhamento. Usa a pilha de `create_dialogs`/`DialogProvider`
+// quando houver; sem ela, cria uma própria, compartilhada com os diálogos aninhados.
+#[component]
+pub fn Dialog(props: DialogProps) -> View {
+    let dialogs = try_use_context::<Dialogs>().unwrap_or_else(create_dialogs);
+    let id = dialogs.next_id();
+    let open = props.open;
+    let dismissible = props.dismissible;
+    let panel = create_node_ref();
+    let previous_focus = create_signal(None::<HtmlElement>);
+
+    create_effect(move || {
+        if open.get() {
+            dialogs.open_layer(id);
+            let active = document().active_element().and_then(|e| e.dyn_into::<HtmlElement>().ok());
+            previous_focus.set_silent(active);
+            sycamore::web::queue_microtask(move || {
+                let Some(node) = panel.try_get() else { return };
+                let panel: HtmlElement = node.unchecked_into();
+                match focusables(&panel).first() {
+                    Some(first) => { let _ = first.focus(); }
+                    None => { let _ = panel.focus(); }
+                }
+            });
+        } else {
+            dialogs.close_layer(id);
+            if let Some(el) = previous_focus.replace_silent(None) {
+                let _ = el.focus();
+            }
+        }
+    });
+    on_cleanup(move || dialogs.close_layer(id));
+
+    let is_top = move || dialogs.stack.with(|s| s.last() == Some(&id));
+    let z_index = move || {
+        let depth = dialogs.stack.with(|s| s.iter().position(|l| *l == id).unwrap_or(0));
+        1100 + depth * 10
+    };
+
+    let on_keydown = move |e: KeyboardEvent| {
+        if !is_top() {
+            return;
+        }
+        match e.key().as_str() {
+            "Escape" if dismissible => {
+                e.stop_propagation();
+                open.set(false);
+            }
+            "Tab" => {
+                let panel: HtmlElement = panel.get().unchecked_into();
+                let items = focusables(&panel);
+                let (Some(first), Some(last)) = (items.first(), items.last()) else {
+                    e.prevent_default();
+                    return;
+                };
+                let active = document().active_element();
+                if e.shift_key() && active.as_ref() == Some(first.as_ref()) {
+                    e.prevent_default();
+                    let _ = last.focus();
+                } else if !e.shift_key() && active.as_ref() == Some(last.as_ref()) {
+                    e.prevent_default();
+                    let _ = first.focus();
+                }
+            }
+            _ => {}
+        }
+    };
+
+    let on_backdrop = move |e: MouseEvent| {
+        if dismissible && e.target() == e.current_target() {
+            open.set(false);
+        }
+    };
+
+    let children = props.children.call();
+    let title = props.title;
+
+    view! {
+        div(
+            class="dialog-backdrop",
+            style=move || format!(
+                "z-index: {}; {}",
+                z_index(),
+                if open.get() { "" } else { "display: none;" }
+            ),
+            on:click=on_backdrop
+        ) {
+            div(
+                r#ref=panel,
+                class="dialog fade-in",
+                role="dialog",
+                aria-modal="true",
+                tabindex="-1",
+                on:keydown=on_keydown
+            ) {
+                (if title.is_empty() {
+                    view! {}
+                } else {
+                    view! { div(class="dialog-title") { span(class="ui-label title") { (title) } } }
+                })
+                div(class="dialog-body") { (children) }
+            }
+        }
+    }
+}
+
+#[derive(Props)]
+pub struct ConfirmDialogProps {
+    pub open: Signal<bool>,
+    pub message: String,
+    pub on_confirm: Rc<dyn Fn()>,
+    #[prop(default = "Confirmação")]
+    pub title: &'static str,
+    #[prop(default = "Confirmar")]
+    pub confirm_text: &'static str,
+    #[prop(default = "Cancelar")]
+    pub cancel_text: &'static str,
+    #[prop(default)]
+    pub destructive: bool,
+}
+
+#[component]
+pub fn ConfirmDialog(props: ConfirmDialogProps) -> View {
+    let open = props.open;
+    let on_confirm = props.on_confirm;
+    let confirm_class = if props.destructive { "btn btn-destructive" } else { "btn btn-primary" };
+
+    view! {
+        Dialog(open = open, title = props.title) {
+            span(class="ui-label body") { (props.message) }
+            div(class="row dialog-actions") {
+                button(class="btn btn-ghost", on:click=move |_| open.set(false)) { (props.cancel_text) }
+                button(
+                    class=confirm_class,
+                    on:click=move |_| {
+                        on_confirm();
+                        open.set(false);
+                    }
+                ) { (props.confirm_text) }
+            }
+        }
+    }
+}
+
+#[derive(Props)]
+pub struct PromptDialogProps {
+    pub open: Signal<bool>,
+    pub message: String,
+    pub value: Signal<String>,
+    pub on_submit: Rc<dyn Fn(String)>,
+    #[prop(default = "Informe um valor")]
+    pub title: &'static str,
+    #[prop(default)]
+    pub placeholder: &'static str,
+}
+
+#[component]
+pub fn PromptDialog(props: PromptDialogProps) -> View {
+    let open = props.open;
+    let value = props.value;
+    let on_submit = props.on_submit;
+    let submit = Rc::new(move || {
+        on_submit(value.get_clone());
+        open.set(false);
+    });
+    let submit_on_enter = submit.clone();
+
+    view! {
+        Dialog(open = open, title = props.title) {
+            span(class="ui-label body") { (props.message) }
+            input(
+                class="textfield",
+                r#type="text",
+                placeholder=props.placeholder,
+                prop:value=move || value.get_clone(),
+                on:input=move |e: Event| {
+                    let target: HtmlInputElement = e.target().unwrap().unchecked_into();
+                    value.set(target.value());
+                },
+                on:keydown=move |e: KeyboardEvent| if e.key() == "Enter" { submit_on_enter() }
+            )
+            div(class="row dialog-actions") {
+                button(class="btn btn-ghost", on:click=move |_| open.set(false)) { "Cancelar" }
+                button(class="btn btn-primary", on:click=move |_| submit()) { "OK" }
+            }
+        }
+    }
+}
+
+// Renderiza os diálogos abertos via `confirm`/`prompt`.
+#[derive(Props)]
+pub struct DialogProviderProps {
+    pub children: Children,
+}
+
+#[component]
+pub fn DialogProvider(props: DialogProviderProps) -> View {
+    let dialogs = try_use_context::<Dialogs>().unwrap_or_else(create_dialogs);
+    dialogs.providers.update(|n| *n += 1);
+    on_cleanup(move || {
+        if dialogs.providers.is_alive() {
+            dialogs.providers.update(|n| *n -= 1);
+            // Sem quem os mostre, os pendentes são cancelados (o `Sender` é descartado)
+            if dialogs.providers.get_untracked() == 0 {
+                dialogs.pending.set(Vec::new());
+            }
+        }
+    });
+    let children = props.children.call();
+
+    view! {
+        (children)
+        Keyed(
+            list=dialogs.pending,
+            view=move |pending| view! { PendingDialogView(pending = pending) },
+            key=|pending| pending.id
+        )
+    }
+}
+
+#[derive(Props)]
+struct PendingDialogViewProps {
+    pending: PendingDialog,
+}
+
+#[component]
+fn PendingDialogView(props: PendingDialogViewProps) -> View {
+    let dialogs = use_dialogs();
+    let open = create_signal(true);
+    let id = props.pending.id;
+    let respond = props.pending.respond.clone();
+    let reply = Rc::new(move |value: Option<String>| {
+        if let Some(tx) = respond.borrow_mut().take() {
+            let _ = tx.send(value);
+        }
+    });
+
+    // Fechar por Escape, fundo ou "Cancelar" conta como recusa
+    create_effect({
+        let reply = reply.clone();
+        move || {
+            if !open.get() {
+                reply(None);
+                sycamore::web::queue_microtask(move || {
+                    dialogs.pending.update(|p| p.retain(|d| d.id != id));
+                });
+            }
+        }
+    });
+
+    match props.pending.request {
+        DialogRequest::Confirm { message, destructive } => {
+            let reply = reply.clone();
+            view! {
+                ConfirmDialog(
+                    open = open,
+                    message = message,
+                    destructive = destructive,
+                    on_confirm = Rc::new(move || reply(Some(String::new())))
+                )
+            }
+        }
+        DialogRequest::Prompt { message, initial } => {
+            let reply = reply.clone();
+            view! {
+                PromptDialog(
+                    open = open,
+                    message = message,
+                    value = create_signal(initial),
+                    on_submit = Rc::new(move |v| reply(Some(v)))
+                )
+            }
+        }
+    }
+}
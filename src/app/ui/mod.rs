@@ -4,13 +4,24 @@ use sycamore::web::events::{Event, MouseEvent};
 use wasm_bindgen::JsCast;
 use web_sys::HtmlInputElement;
 
+mod toast;
+mod data_table;
+mod virtual_list;
+mod dialog;
+mod theme;
+pub use toast::*;
+pub use data_table::*;
+pub use virtual_list::*;
+pub use dialog::*;
+pub use theme::*;
+
 // --- UTILS & ENUMS ---
 
 #[derive(Clone, Copy)]
 pub enum Align { Start, Center, End, Stretch, Between }
 
 impl Align {
-    fn to_css(&self) -> &'static str {
+    fn to_css(self) -> &'static str {
         match self {
             Align::Start => "flex-start",
             Align::Center => "center",
@@ -227,11 +238,13 @@ impl TabItem {
 #[derive(Props)]
 pub struct TabViewProps {
     pub tabs: Vec<TabItem>,
+    #[prop(default)]
+    pub active: Signal<usize>,
 }
 
 #[component]
 pub fn TabView(props: TabViewProps) -> View {
-    let active_idx = create_signal(0);
+    let active_idx = props.active;
     let headers: Vec<(usize, &'static str)> = props.tabs.iter()
         .enumerate()
         .map(|(i, t)| (i, t.title))
@@ -255,6 +268,8 @@ pub fn TabView(props: TabViewProps) -> View {
                 )
             }
             div(class="tab-content") {
+                // Cada troca descarta o escopo da aba anterior, disparando os `on_cleanup`
+                // dela (ex.: `AbortHandle::scoped` cancela as requisições pendentes)
                 (move || {
                     let idx = active_idx.get();
                     if let Some(tab) = props.tabs.get(idx) {
@@ -307,6 +322,10 @@ pub struct TextFieldProps {
     pub disabled: bool,
     #[prop(default)]
     pub password: bool,
+    #[prop(default)]
+    pub error: Signal<Option<String>>,
+    #[prop(default)]
+    pub touched: Signal<bool>,
 }
 
 #[component]
@@ -317,17 +336,23 @@ pub fn TextField(props: TextFieldProps) -> View {
     };
     let style = if props.grow { "flex-grow: 1;" } else { "" };
     let input_type = if props.password { "password" } else { "text" };
+    let error = props.error;
     
     view! {
         input(
-            class="textfield",
+            class=move || if error.with(|e| e.is_some()) { "textfield invalid" } else { "textfield" },
             r#type=input_type,
             placeholder=props.placeholder,
             prop:value=props.value.get_clone(),
             on:input=on_input,
+            on:blur=move |_| props.touched.set(true),
             style=style,
             disabled=props.disabled
         )
+        (move || match error.get_clone() {
+            Some(message) => view! { span(class="field-error") { (message) } },
+            None => view! {},
+        })
     }
 }
 
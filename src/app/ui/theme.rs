@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sycamore::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, MediaQueryList};
+
+// --- TIPOS ---
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum ThemeMode { #[default] Light, Dark, Auto }
+
+// Cada campo vira a variável CSS de mesmo nome em `:root` (`bg_app` -> `--bg-app`).
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Theme {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(skip)]
+    pub dark: bool,
+
+    pub bg_app: String,
+    pub bg_window: String,
+    pub bg_surface: String,
+    pub bg_surface_secondary: String,
+    pub bg_surface_hover: String,
+    pub bg_sidebar: String,
+
+    pub text_primary: String,
+    pub text_secondary: String,
+    pub text_tertiary: String,
+    pub text_inverse: String,
+
+    pub primary: String,
+    pub primary_hover: String,
+    pub primary_light: String,
+    pub success: String,
+    pub success_light: String,
+    pub warning: String,
+    pub warning_light: String,
+    pub danger: String,
+    pub danger_light: String,
+    pub info: String,
+    pub info_light: String,
+
+    pub border_light: String,
+    pub border_medium: String,
+    pub border_focus: String,
+
+    pub shadow_window: String,
+    pub shadow_sm: String,
+    pub shadow_md: String,
+    pub shadow_lg: String,
+
+    pub radius_window: String,
+    pub radius_component: String,
+    pub radius_small: String,
+
+    pub font_sans: String,
+    pub font_mono: String,
+}
+
+impl Theme {
+    // Mesmos valores do `:root` em `_styles.css`.
+    pub fn light() -> Self {
+        Self {
+            name: "Claro".to_string(),
+            dark: false,
+            bg_app: "#e8eaed".to_string(),
+            bg_window: "#ffffff".to_string(),
+            bg_surface: "#ffffff".to_string(),
+            bg_surface_secondary: "#f8f9fa".to_string(),
+            bg_surface_hover: "#f1f3f4".to_string(),
+            bg_sidebar: "#f5f5f5".to_string(),
+            text_primary: "#202124".to_string(),
+            text_secondary: "#5f6368".to_string(),
+            text_tertiary: "#80868b".to_string(),
+            text_inverse: "#ffffff".to_string(),
+            primary: "#1a73e8".to_string(),
+            primary_hover: "#1557b0".to_string(),
+            primary_light: "#e8f0fe".to_string(),
+            success: "#1e8e3e".to_string(),
+            success_light: "#e6f4ea".to_string(),
+            warning: "#f9ab00".to_string(),
+            warning_light: "#fef7e0".to_string(),
+            danger: "#d93025".to_string(),
+            danger_light: "#fce8e6".to_string(),
+            info: "#1967d2".to_string(),
+            info_light: "#e8f0fe".to_string(),
+            border_light: "#dadce0".to_string(),
+            border_medium: "#bdc1c6".to_string(),
+            border_focus: "#1a73e8".to_string(),
+            shadow_window: "0 8px 16px rgba(0, 0, 0, 0.1), 0 0 1px rgba(0, 0, 0, 0.1)".to_string(),
+            shadow_sm: "0 1px 2px 0 rgba(60, 64, 67, 0.3), 0 1px 3px 1px rgba(60, 64, 67, 0.15)".to_string(),
+            shadow_md: "0 1px 3px 0 rgba(60, 64, 67, 0.3), 0 4px 8px 3px rgba(60, 64, 67, 0.15)".to_string(),
+            shadow_lg: "0 8px 10px 1px rgba(60, 64, 67, 0.15), 0 3px 14px 2px rgba(60, 64, 67, 0.12)".to_string(),
+            radius_window: "12px".to_string(),
+            radius_component: "8px".to_string(),
+            radius_small: "4px".to_string(),
+            font_sans: "'Segoe UI', 'Roboto', system-ui, -apple-system, sans-serif".to_string(),
+            font_mono: "'Consolas', 'Monaco', 'Courier New', monospace".to_string(),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            name: "Escuro".to_string(),
+            dark: true,
+            bg_app: "#121212".to_string(),
+            bg_window: "#1e1f22".to_string(),
+            bg_surface: "#26272b".to_string(),
+            bg_surface_secondary: "#2b2d31".to_string(),
+            bg_surface_hover: "#33353a".to_string(),
+            bg_sidebar: "#202124".to_string(),
+            text_primary: "#e8eaed".to_string(),
+            text_secondary: "#bdc1c6".to_string(),
+            text_tertiary: "#9aa0a6".to_string(),
+            text_inverse: "#202124".to_string(),
+            primary: "#8ab4f8".to_string(),
+            primary_hover: "#aecbfa".to_string(),
+            primary_light: "#28354a".to_string(),
+            success: "#81c995".to_string(),
+            success_light: "#23352a".to_string(),
+            warning: "#fdd663".to_string(),
+            warning_light: "#3d3520".to_string(),
+            danger: "#f28b82".to_string(),
+            danger_light: "#3f2624".to_string(),
+            info: "#8ab4f8".to_string(),
+            info_light: "#28354a".to_string(),
+            border_light: "#3c4043".to_string(),
+            border_medium: "#5f6368".to_string(),
+            border_focus: "#8ab4f8".to_string(),
+            shadow_window: "0 8px 16px rgba(0, 0, 0, 0.5), 0 0 1px rgba(0, 0, 0, 0.6)".to_string(),
+            shadow_sm: "0 1px 2px 0 rgba(0, 0, 0, 0.6), 0 1px 3px 1px rgba(0, 0, 0, 0.3)".to_string(),
+            shadow_md: "0 1px 3px 0 rgba(0, 0, 0, 0.6), 0 4px 8px 3px rgba(0, 0, 0, 0.3)".to_string(),
+            shadow_lg: "0 8px 10px 1px rgba(0, 0, 0, 0.3), 0 3px 14px 2px rgba(0, 0, 0, 0.25)".to_string(),
+            ..Self::light()
+        }
+    }
+
+    // Tema customizado: `{"name": "...", "dark": true, "primary": "#ff5722", ...}`.
+    // Chaves ausentes herdam da paleta base (clara ou escura, conforme `dark`).
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let mut custom: Value = serde_json::from_str(json)?;
+        let name = custom.get("name").and_then(Value::as_str).unwrap_or("Personalizado").to_string();
+        let dark = custom.get("dark").and_then(Value::as_bool).unwrap_or(false);
+        let base = if dark { Self::dark() } else { Self::light() };
+
+        let mut merged = serde_json::to_value(&base)?;
+        if let (Some(target), Some(source)) = (merged.as_object_mut(), custom.as_object_mut()) {
+            for (key, value) in std::mem::take(source) {
+                if target.contains_key(&key) {
+                    target.insert(key, value);
+                }
+            }
+        }
+
+        let mut theme: Self = serde_json::from_value(merged)?;
+        theme.name = name;
+        theme.dark = dark;
+        Ok(theme)
+    }
+
+    pub fn css_vars(&self) -> Vec<(String, String)> {
+        let Ok(Value::Object(tokens)) = serde_json::to_value(self) else { return Vec::new() };
+        tokens.into_iter()
+            .filter_map(|(key, value)| value.as_str().map(|v| (format!("--{}", key), v.to_string())))
+            .collect()
+    }
+}
+
+// --- ESTADO ---
+
+#[derive(Clone, Copy)]
+pub struct ThemeHandle {
+    pub mode: Signal<ThemeMode>,
+    // Paletas usadas por Light/Dark; temas JSON substituem uma delas
+    pub light: Signal<Theme>,
+    pub dark: Signal<Theme>,
+    // Espelha `prefers-color-scheme: dark`, usado no modo Auto
+    pub system_dark: Signal<bool>,
+}
+
+impl ThemeHandle {
+    pub fn is_dark(&self) -> bool {
+        match self.mode.get() {
+            ThemeMode::Light => false,
+            ThemeMode::Dark => true,
+            ThemeMode::Auto => self.system_dark.get(),
+        }
+    }
+
+    pub fn active(&self) -> Theme {
+        if self.is_dark() { self.dark.get_clone() } else { self.light.get_clone() }
+    }
+
+    pub fn set_mode(&self, mode: ThemeMode) {
+        self.mode.set(mode);
+    }
+
+    pub fn set_theme(&self, theme: Theme) {
+        if theme.dark { self.dark.set(theme) } else { self.light.set(theme) }
+    }
+
+    pub fn load_json(&self, json: &str) -> Result<(), serde_json::Error> {
+        self.set_theme(Theme::from_json(json)?);
+        Ok(())
+    }
+
+    // Volta às paletas embutidas.
+    pub fn reset(&self) {
+        self.light.set(Theme::light());
+        self.dark.set(Theme::dark());
+    }
+}
+
+fn dark_query() -> Option<MediaQueryList> {
+    window().match_media("(prefers-color-scheme: dark)").ok().flatten()
+}
+
+pub fn create_theme(mode: ThemeMode) -> ThemeHandle {
+    let query = dark_query();
+    let theme = ThemeHandle {
+        mode: create_signal(mode),
+        light: create_signal(Theme::light()),
+        dark: create_signal(Theme::dark()),
+        system_dark: create_signal(query.as_ref().is_some_and(|q| q.matches())),
+    };
+    provide_context(theme);
+
+    if let Some(query) = query {
+        let listener = Closure::<dyn Fn(web_sys::MediaQueryListEvent)>::new(move |e: web_sys::MediaQueryListEvent| {
+            theme.system_dark.set(e.matches());
+        });
+        let _ = query.add_event_listener_with_callback("change", listener.as_ref().unchecked_ref());
+        on_cleanup(move || {
+            let _ = query.remove_event_listener_with_callback("change", listener.as_ref().unchecked_ref());
+        });
+    }
+
+    theme
+}
+
+pub fn use_theme() -> ThemeHandle {
+    use_context::<ThemeHandle>()
+}
+
+// --- PROVIDER ---
+
+#[derive(Props)]
+pub struct ThemeProviderProps {
+    pub children: Children,
+    #[prop(default)]
+    pub mode: ThemeMode,
+}
+
+// Escreve o tema ativo nas variáveis de `:root` sempre que ele muda.
+#[component]
+pub fn ThemeProvider(props: ThemeProviderProps) -> View {
+    let theme = try_use_context::<ThemeHandle>().unwrap_or_else(|| create_theme(props.mode));
+
+    create_effect(move || {
+        let active = theme.active();
+        let Some(root) = document().document_element() else { return };
+        let Ok(root) = root.dyn_into::<HtmlElement>() else { return };
+        let style = root.style();
+        for (name, value) in active.css_vars() {
+            let _ = style.set_property(&name, &value);
+        }
+        let _ = style.set_property("color-scheme", if active.dark { "dark" } else { "light" });
+        let _ = root.set_attribute("data-theme", if active.dark { "dark" } else { "light" });
+    });
+
+    props.children.call()
+}
@@ -0,0 +1,258 @@
+use std::rc::Rc;
+use sycamore::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use super::BadgeKind;
+
+// --- TIPOS ---
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum ToastPosition { TopLeft, TopRight, BottomLeft, #[default] BottomRight }
+
+impl ToastPosition {
+    fn to_css(self) -> &'static str {
+        match self {
+            ToastPosition::TopLeft => "top: 16px; left: 16px;",
+            ToastPosition::TopRight => "top: 16px; right: 16px;",
+            ToastPosition::BottomLeft => "bottom: 16px; left: 16px;",
+            ToastPosition::BottomRight => "bottom: 16px; right: 16px;",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ToastAction {
+    pub label: &'static str,
+    pub on_click: Rc<dyn Fn()>,
+}
+
+#[derive(Clone)]
+pub struct Toast {
+    id: u32,
+    // Mesma escala de cores do `Badge`
+    pub kind: BadgeKind,
+    pub title: Option<String>,
+    pub message: String,
+    pub duration_ms: Option<u32>,
+    pub actions: Vec<ToastAction>,
+}
+
+impl PartialEq for Toast {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Toast {
+    pub fn new(kind: BadgeKind, message: impl Into<String>) -> Self {
+        Self {
+            id: 0,
+            kind,
+            title: None,
+            message: message.into(),
+            duration_ms: Some(4000),
+            actions: Vec::new(),
+        }
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn duration(mut self, ms: u32) -> Self {
+        self.duration_ms = Some(ms);
+        self
+    }
+
+    // Fica na tela até o usuário fechar ou uma ação ser clicada.
+    pub fn persistent(mut self) -> Self {
+        self.duration_ms = None;
+        self
+    }
+
+    pub fn action<F>(mut self, label: &'static str, on_click: F) -> Self
+    where F: Fn() + 'static {
+        self.actions.push(ToastAction { label, on_click: Rc::new(on_click) });
+        self
+    }
+}
+
+// --- FILA GLOBAL ---
+
+#[derive(Clone, Copy)]
+pub struct Toasts {
+    queue: Signal<Vec<Toast>>,
+    next_id: Signal<u32>,
+    max_visible: Signal<usize>,
+}
+
+impl Toasts {
+    pub fn push(&self, mut toast: Toast) -> u32 {
+        let id = self.next_id.get_untracked();
+        self.next_id.set(id + 1);
+        toast.id = id;
+
+        if let Some(ms) = toast.duration_ms {
+            let toasts = *self;
+            set_timeout(ms, move || toasts.dismiss(id));
+        }
+
+        self.queue.update(|q| q.push(toast));
+        self.trim();
+        id
+    }
+
+    // Descarta os mais antigos acima do limite.
+    fn trim(&self) {
+        let max = self.max_visible.get_untracked();
+        if self.queue.with_untracked(|q| q.len() > max) {
+            self.queue.update(|q| {
+                let excess = q.len() - max;
+                q.drain(..excess);
+            });
+        }
+    }
+
+    // O `ToastProvider` aplica o seu `max_visible` aqui, mesmo numa fila criada antes
+    // com `create_toasts`.
+    pub fn set_max_visible(&self, max_visible: usize) {
+        self.max_visible.set(max_visible);
+        self.trim();
+    }
+
+    pub fn info(&self, message: impl Into<String>) -> u32 {
+        self.push(Toast::new(BadgeKind::Info, message))
+    }
+
+    pub fn success(&self, message: impl Into<String>) -> u32 {
+        self.push(Toast::new(BadgeKind::Success, message))
+    }
+
+    pub fn warning(&self, message: impl Into<String>) -> u32 {
+        self.push(Toast::new(BadgeKind::Warning, message))
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> u32 {
+        self.push(Toast::new(BadgeKind::Danger, message).duration(8000))
+    }
+
+    pub fn dismiss(&self, id: u32) {
+        // O timer pode disparar depois que o provider foi desmontado
+        if self.queue.is_alive() {
+            self.queue.update(|q| q.retain(|t| t.id != id));
+        }
+    }
+
+    pub fn clear(&self) {
+        self.queue.set(Vec::new());
+    }
+}
+
+// Cria a fila no escopo atual; útil quando quem dispara os toasts está acima do `ToastProvider`.
+pub fn create_toasts(max_visible: usize) -> Toasts {
+    let toasts = Toasts {
+        queue: create_signal(Vec::new()),
+        next_id: create_signal(1),
+        max_visible: create_signal(max_visible),
+    };
+    provide_context(toasts);
+    toasts
+}
+
+pub fn use_toasts() -> Toasts {
+    use_context::<Toasts>()
+}
+
+pub(crate) fn set_timeout(ms: u32, f: impl FnOnce() + 'static) {
+    let callback = Closure::once_into_js(f);
+    let _ = window().set_timeout_with_callback_and_timeout_and_arguments_0(
+        callback.unchecked_ref(),
+        ms as i32,
+    );
+}
+
+// --- COMPONENTES ---
+
+#[derive(Props)]
+pub struct ToastProviderProps {
+    pub children: Children,
+    #[prop(default)]
+    pub position: ToastPosition,
+    #[prop(default = 5)]
+    pub max_visible: usize,
+}
+
+#[component]
+pub fn ToastProvider(props: ToastProviderProps) -> View {
+    let toasts = try_use_context::<Toasts>().unwrap_or_else(|| create_toasts(props.max_visible));
+    toasts.set_max_visible(props.max_visible);
+
+    let children = props.children.call();
+    let reverse = matches!(props.position, ToastPosition::BottomLeft | ToastPosition::BottomRight);
+    let style = format!(
+        "{} flex-direction: {};",
+        props.position.to_css(),
+        if reverse { "column-reverse" } else { "column" }
+    );
+
+    view! {
+        (children)
+        div(class="toast-stack", style=style) {
+            Keyed(
+                list=toasts.queue,
+                view=move |toast| view! { ToastCard(toast = toast) },
+                key=|toast| toast.id
+            )
+        }
+    }
+}
+
+#[derive(Props)]
+struct ToastCardProps {
+    toast: Toast,
+}
+
+#[component]
+fn ToastCard(props: ToastCardProps) -> View {
+    let toasts = use_toasts();
+    let id = props.toast.id;
+    let kind_class = match props.toast.kind {
+        BadgeKind::Primary => "toast-primary",
+        BadgeKind::Info => "toast-info",
+        BadgeKind::Success => "toast-success",
+        BadgeKind::Warning => "toast-warning",
+        BadgeKind::Danger => "toast-danger",
+    };
+
+    let title = match props.toast.title {
+        Some(title) => view! { span(class="ui-label title") { (title) } },
+        None => view! {},
+    };
+    let actions = if props.toast.actions.is_empty() {
+        view! {}
+    } else {
+        let buttons = props.toast.actions.into_iter().map(|action| view! {
+            button(
+                class="btn btn-ghost",
+                on:click=move |_| {
+                    (action.on_click)();
+                    toasts.dismiss(id);
+                }
+            ) { (action.label) }
+        }).collect::<Vec<_>>();
+        view! { div(class="row toast-actions") { (buttons) } }
+    };
+
+    view! {
+        div(class=format!("toast fade-in {}", kind_class), role="status") {
+            div(class="column toast-body") {
+                (title)
+                span(class="ui-label body") { (props.toast.message) }
+                (actions)
+            }
+            button(class="toast-close", title="Fechar", on:click=move |_| toasts.dismiss(id)) { "×" }
+        }
+    }
+}
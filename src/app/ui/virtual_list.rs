@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+use sycamore::prelude::*;
+use sycamore::web::events::Event;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+
+// --- TIPOS ---
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum RowHeight {
+    Fixed(f64),
+    // Altura estimada até a linha ser renderizada e medida.
+    Measured(f64),
+}
+
+// Deslocamento acumulado: offsets[i] é o topo da linha i, offsets[n] a altura total.
+fn first_visible(offsets: &[f64], scroll_top: f64) -> usize {
+    offsets.partition_point(|&top| top <= scroll_top).saturating_sub(1)
+}
+
+// --- LISTA VIRTUALIZADA ---
+
+#[derive(Props)]
+pub struct VirtualListProps<T: 'static, K: 'static> {
+    pub items: Signal<Vec<T>>,
+    pub view: Rc<dyn Fn(T) -> View>,
+    // Identidade estável da linha; mantém medições e a posição ao inserir acima.
+    pub key: Rc<dyn Fn(&T) -> K>,
+    #[prop(default = 400)]
+    pub height: u32,
+    #[prop(default = RowHeight::Fixed(32.0))]
+    pub row_height: RowHeight,
+    #[prop(default = 5)]
+    pub overscan: usize,
+}
+
+#[component]
+pub fn VirtualList<T, K>(props: VirtualListProps<T, K>) -> View
+where
+    T: Clone + 'static,
+    K: Clone + Eq + Hash + 'static,
+{
+    let items = props.items;
+    let render_row = props.view;
+    let key = props.key;
+    let row_height = props.row_height;
+    let overscan = props.overscan;
+    let viewport = props.height as f64;
+
+    let container = create_node_ref();
+    let rows_ref = create_node_ref();
+    let scroll_top = create_signal(0.0f64);
+    let measured = create_signal(HashMap::<K, f64>::new());
+    // Primeira linha visível e quanto dela já rolou para fora da tela
+    let anchor = create_signal(None::<(K, f64)>);
+
+    let offsets = create_memo({
+        let key = key.clone();
+        move || {
+            items.with(|items| {
+                let mut offsets = Vec::with_capacity(items.len() + 1);
+                let mut top = 0.0;
+                offsets.push(top);
+                match row_height {
+                    RowHeight::Fixed(h) => {
+                        for _ in items {
+                            top += h;
+                            offsets.push(top);
+                        }
+                    }
+                    RowHeight::Measured(estimate) => measured.with(|sizes| {
+                        for item in items {
+                            top += sizes.get(&key(item)).copied().unwrap_or(estimate);
+                            offsets.push(top);
+                        }
+                    }),
+                }
+                offsets
+            })
+        }
+    });
+
+    let range = create_memo(move || {
+        let top = scroll_top.get();
+        offsets.with(|offsets| {
+            let count = offsets.len() - 1;
+            let first = first_visible(offsets, top).min(count);
+            let last = offsets.partition_point(|&t| t < top + viewport).min(count);
+            (first.saturating_sub(overscan), (last + overscan).min(count))
+        })
+    });
+
+    // Linhas inseridas (ou remedidas) acima da viewport empurram o conteúdo;
+    // compensa o scroll para a linha âncora continuar no mesmo lugar.
+    create_effect({
+        let key = key.clone();
+        move || {
+            offsets.track();
+            let Some((anchor_key, delta)) = anchor.get_clone_untracked() else { return };
+            let target = items.with_untracked(|items| {
+                let idx = items.iter().position(|item| key(item) == anchor_key)?;
+                offsets.with_untracked(|o| o.get(idx).copied())
+            });
+            let Some(target) = target.map(|top| top + delta) else { return };
+            if (target - scroll_top.get_untracked()).abs() > 0.5 {
+                if let Some(el) = container.try_get() {
+                    el.unchecked_into::<HtmlElement>().set_scroll_top(target as i32);
+                }
+                scroll_top.set(target);
+            }
+        }
+    });
+
+    let on_scroll = {
+        let key = key.clone();
+        move |e: Event| {
+            let el: HtmlElement = e.target().unwrap().unchecked_into();
+            let top = el.scroll_top() as f64;
+            let next = offsets.with_untracked(|offsets| {
+                let idx = first_visible(offsets, top);
+                items.with_untracked(|items| {
+                    items.get(idx).map(|item| (key(item), top - offsets[idx]))
+                })
+            });
+            anchor.set_silent(next);
+            scroll_top.set(top);
+        }
+    };
+
+    let measure = {
+        let key = key.clone();
+        move || {
+            let Some(rows) = rows_ref.try_get() else { return };
+            let children = rows.unchecked_into::<HtmlElement>().children();
+            let mut changes = Vec::new();
+
+            for i in 0..children.length() {
+                let Some(child) = children.item(i) else { continue };
+                let Some(idx) = child.get_attribute("data-index").and_then(|v| v.parse::<usize>().ok()) else { continue };
+                let height = child.unchecked_into::<HtmlElement>().offset_height() as f64;
+                let Some(k) = items.with_untracked(|items| items.get(idx).map(|item| key(item))) else { continue };
+                if measured.with_untracked(|m| m.get(&k) != Some(&height)) {
+                    changes.push((k, height));
+                }
+            }
+
+            if !changes.is_empty() {
+                measured.update(|m| m.extend(changes));
+            }
+        }
+    };
+
+    let window = move || {
+        let (start, end) = range.get();
+        let translate = offsets.with(|o| o[start]);
+        let slice: Vec<T> = items.with_untracked(|items| items[start..end].to_vec());
+
+        let rows = slice.into_iter().enumerate().map(|(i, item)| {
+            let style = match row_height {
+                RowHeight::Fixed(h) => format!("height: {}px; overflow: hidden;", h),
+                RowHeight::Measured(_) => String::new(),
+            };
+            let content = render_row(item);
+            view! {
+                div(class="virtual-row", data-index=(start + i).to_string(), style=style) {
+                    (content)
+                }
+            }
+        }).collect::<Vec<_>>();
+
+        if matches!(row_height, RowHeight::Measured(_)) {
+            let measure = measure.clone();
+            sycamore::web::queue_microtask(measure);
+        }
+
+        view! {
+            div(r#ref=rows_ref, style=format!("transform: translateY({}px);", translate)) {
+                (rows)
+            }
+        }
+    };
+
+    view! {
+        div(
+            r#ref=container,
+            class="virtual-list",
+            style=format!("height: {}px;", props.height),
+            on:scroll=on_scroll
+        ) {
+            div(style=move || format!(
+                "height: {}px;",
+                offsets.with(|o| o.last().copied().unwrap_or_default())
+            )) {
+                (window)
+            }
+        }
+    }
+}
@@ -0,0 +1,557 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::{Rc, Weak};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sycamore::futures::spawn_local;
+use sycamore::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{console, MessageEvent, WebSocket};
+
+use super::http::{backoff_delay, sleep};
+
+// --- ESTADO ---
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WsState {
+    Connecting,
+    Open,
+    // Caiu e vai tentar de novo; `attempt` começa em 1
+    Reconnecting { attempt: u32 },
+    // `close()`, escopo descartado ou tentativas esgotadas
+    Closed,
+}
+
+impl WsState {
+    pub fn is_open(&self) -> bool {
+        matches!(self, WsState::Open)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum WsError {
+    Encode(String),
+    // O client foi fechado; nada mais é enfileirado
+    Closed,
+}
+
+impl std::fmt::Display for WsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WsError::Encode(msg) => write!(f, "Erro ao serializar mensagem: {}", msg),
+            WsError::Closed => write!(f, "Conexão encerrada"),
+        }
+    }
+}
+
+// --- OPÇÕES ---
+
+#[derive(Debug, Clone)]
+pub struct WsOptions {
+    pub base_delay_ms: u32,
+    pub max_delay_ms: u32,
+    // `None` = tenta para sempre
+    pub max_retries: Option<u32>,
+    // Intervalo do ping. Sem nenhuma mensagem até o próximo ping, a conexão é
+    // considerada morta e reaberta.
+    pub heartbeat_ms: Option<u32>,
+    pub ping: String,
+    // Respostas do servidor ao ping; não chegam aos handlers
+    pub pong: String,
+    // Mensagens guardadas enquanto desconectado; as mais antigas são descartadas
+    pub max_queue: usize,
+}
+
+impl Default for WsOptions {
+    fn default() -> Self {
+        Self {
+            base_delay_ms: 500,
+            max_delay_ms: 30_000,
+            max_retries: None,
+            heartbeat_ms: Some(25_000),
+            ping: r#"{"type":"ping"}"#.to_string(),
+            pong: r#"{"type":"pong"}"#.to_string(),
+            max_queue: 100,
+        }
+    }
+}
+
+impl WsOptions {
+    pub fn backoff(mut self, base_ms: u32, max_ms: u32) -> Self {
+        self.base_delay_ms = base_ms;
+        self.max_delay_ms = max_ms.max(base_ms);
+        self
+    }
+
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = Some(retries);
+        self
+    }
+
+    pub fn heartbeat(mut self, interval_ms: u32, ping: impl Into<String>, pong: impl Into<String>) -> Self {
+        self.heartbeat_ms = Some(interval_ms);
+        self.ping = ping.into();
+        self.pong = pong.into();
+        self
+    }
+
+    pub fn no_heartbeat(mut self) -> Self {
+        self.heartbeat_ms = None;
+        self
+    }
+
+    pub fn queue_limit(mut self, max: usize) -> Self {
+        self.max_queue = max;
+        self
+    }
+}
+
+// --- CONEXÃO ---
+
+type SocketCallback = Closure<dyn FnMut(JsValue)>;
+
+struct Inner {
+    url: String,
+    options: WsOptions,
+    state: Signal<WsState>,
+    socket: RefCell<Option<WebSocket>>,
+    // Mantém vivos os callbacks do socket atual
+    callbacks: RefCell<Vec<SocketCallback>>,
+    queue: RefCell<VecDeque<String>>,
+    // Incrementa a cada conexão; timers de uma conexão antiga param sozinhos
+    generation: Cell<u32>,
+    attempt: Cell<u32>,
+    last_seen: Cell<f64>,
+    closed: Cell<bool>,
+    dispatch: Box<dyn Fn(&str)>,
+}
+
+impl Inner {
+    fn open_socket(&self) -> Option<WebSocket> {
+        let socket = self.socket.borrow().clone()?;
+        (socket.ready_state() == WebSocket::OPEN).then_some(socket)
+    }
+
+    fn send_text(&self, text: String) {
+        if let Some(socket) = self.open_socket() {
+            if socket.send_with_str(&text).is_ok() {
+                return;
+            }
+        }
+        let mut queue = self.queue.borrow_mut();
+        queue.push_back(text);
+        while queue.len() > self.options.max_queue {
+            queue.pop_front();
+        }
+    }
+
+    fn flush(&self) {
+        let Some(socket) = self.open_socket() else { return };
+        loop {
+            let Some(text) = self.queue.borrow_mut().pop_front() else { return };
+            if socket.send_with_str(&text).is_err() {
+                self.queue.borrow_mut().push_front(text);
+                return;
+            }
+        }
+    }
+
+    // Desliga os callbacks antes de soltá-los; o socket antigo não avisa mais nada.
+    fn drop_socket(&self) {
+        if let Some(socket) = self.socket.take() {
+            socket.set_onopen(None);
+            socket.set_onmessage(None);
+            socket.set_onclose(None);
+            let _ = socket.close();
+        }
+        self.callbacks.borrow_mut().clear();
+    }
+
+    fn shutdown(&self) {
+        self.closed.set(true);
+        self.generation.set(self.generation.get().wrapping_add(1));
+        self.drop_socket();
+    }
+}
+
+fn connect_socket(inner: &Rc<Inner>) {
+    if inner.closed.get() {
+        return;
+    }
+    inner.drop_socket();
+    let generation = inner.generation.get().wrapping_add(1);
+    inner.generation.set(generation);
+
+    let socket = match WebSocket::new(&inner.url) {
+        Ok(socket) => socket,
+        Err(err) => {
+            console::warn_2(&"WebSocket: URL inválida".into(), &err);
+            inner.shutdown();
+            inner.state.set(WsState::Closed);
+            return;
+        }
+    };
+
+    let weak = Rc::downgrade(inner);
+    let on_open = callback(&weak, move |inner, _| {
+        inner.attempt.set(0);
+        inner.last_seen.set(js_sys::Date::now());
+        inner.state.set(WsState::Open);
+        inner.flush();
+        start_heartbeat(inner, generation);
+    });
+    let on_message = callback(&weak, |inner, event| {
+        inner.last_seen.set(js_sys::Date::now());
+        let Some(text) = event.unchecked_into::<MessageEvent>().data().as_string() else { return };
+        if text != inner.options.ping && text != inner.options.pong {
+            (inner.dispatch)(&text);
+        }
+    });
+    let on_close = callback(&weak, move |inner, _| {
+        if inner.generation.get() == generation {
+            schedule_reconnect(inner);
+        }
+    });
+
+    socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+    socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+    *inner.callbacks.borrow_mut() = vec![on_open, on_message, on_close];
+    *inner.socket.borrow_mut() = Some(socket);
+}
+
+// `Weak` para o socket não manter o client vivo depois de descartado.
+fn callback(weak: &Weak<Inner>, f: impl Fn(&Rc<Inner>, JsValue) + 'static) -> SocketCallback {
+    let weak = weak.clone();
+    Closure::new(move |event: JsValue| {
+        if let Some(inner) = weak.upgrade() {
+            f(&inner, event);
+        }
+    })
+}
+
+fn schedule_reconnect(inner: &Rc<Inner>) {
+    if inner.closed.get() {
+        return;
+    }
+    let attempt = inner.attempt.get() + 1;
+    if inner.options.max_retries.is_some_and(|max| attempt > max) {
+        inner.shutdown();
+        inner.state.set(WsState::Closed);
+        return;
+    }
+    inner.attempt.set(attempt);
+    inner.state.set(WsState::Reconnecting { attempt });
+
+    let generation = inner.generation.get();
+    let delay = backoff_delay(inner.options.base_delay_ms, inner.options.max_delay_ms, attempt);
+    let weak = Rc::downgrade(inner);
+    spawn_local(async move {
+        sleep(delay).await;
+        if let Some(inner) = weak.upgrade() {
+            if inner.generation.get() == generation {
+                connect_socket(&inner);
+            }
+        }
+    });
+}
+
+fn start_heartbeat(inner: &Rc<Inner>, generation: u32) {
+    let Some(interval) = inner.options.heartbeat_ms else { return };
+    let weak = Rc::downgrade(inner);
+    spawn_local(async move {
+        let mut last_ping = js_sys::Date::now();
+        loop {
+            sleep(interval).await;
+            let Some(inner) = weak.upgrade() else { return };
+            if inner.generation.get() != generation {
+                return;
+            }
+            if inner.last_seen.get() < last_ping {
+                // Nem o pong nem outra mensagem: conexão morta sem `close`
+                console::warn_1(&format!("WebSocket: sem resposta em {}ms, reconectando", interval).into());
+                inner.drop_socket();
+                schedule_reconnect(&inner);
+                return;
+            }
+            if let Some(socket) = inner.open_socket() {
+                last_ping = js_sys::Date::now();
+                let _ = socket.send_with_str(&inner.options.ping);
+            }
+        }
+    });
+}
+
+// --- CLIENTE ---
+
+type Handlers<In> = Rc<RefCell<Vec<(u32, Rc<dyn Fn(&Rc<In>)>)>>>;
+
+// Canal JSON tipado sobre WebSocket, com reconexão e fila de saída:
+//
+//     let ws = WsClient::<Evento, Comando>::connect("wss://api/ws", WsOptions::default());
+//     ws.on_message(|evento| console::log_1(&format!("{:?}", evento).into()));
+//     ws.send(&Comando::Assinar { canal: "vendas".into() })?;
+//     let estado = ws.state(); // para o `StatusBar`
+//
+// Mensagens enviadas fora do ar ficam na fila e saem na ordem ao reconectar.
+// A conexão fecha sozinha quando o escopo reativo que a criou for descartado.
+pub struct WsClient<In, Out> {
+    inner: Rc<Inner>,
+    handlers: Handlers<In>,
+    next_id: Rc<Cell<u32>>,
+    _out: std::marker::PhantomData<fn(&Out)>,
+}
+
+impl<In, Out> Clone for WsClient<In, Out> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            handlers: self.handlers.clone(),
+            next_id: self.next_id.clone(),
+            _out: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<In: DeserializeOwned + 'static, Out: Serialize> WsClient<In, Out> {
+    pub fn connect(url: impl Into<String>, options: WsOptions) -> Self {
+        let handlers: Handlers<In> = Rc::default();
+        let dispatch = {
+            let handlers = handlers.clone();
+            move |text: &str| match serde_json::from_str::<In>(text) {
+                Ok(message) => {
+                    let message = Rc::new(message);
+                    // Copia a lista: um handler pode se inscrever ou sair durante a chamada
+                    let current: Vec<_> = handlers.borrow().iter().map(|(_, h)| h.clone()).collect();
+                    for handler in current {
+                        handler(&message);
+                    }
+                }
+                Err(err) => console::warn_1(&format!("WebSocket: mensagem ignorada ({}): {}", err, text).into()),
+            }
+        };
+
+        let inner = Rc::new(Inner {
+            url: url.into(),
+            options,
+            state: create_signal(WsState::Connecting),
+            socket: RefCell::new(None),
+            callbacks: RefCell::new(Vec::new()),
+            queue: RefCell::new(VecDeque::new()),
+            generation: Cell::new(0),
+            attempt: Cell::new(0),
+            last_seen: Cell::new(0.0),
+            closed: Cell::new(false),
+            dispatch: Box::new(dispatch),
+        });
+        connect_socket(&inner);
+
+        // Voltou a rede: não espera o backoff
+        let weak = Rc::downgrade(&inner);
+        let on_online = Closure::<dyn Fn()>::new(move || {
+            if let Some(inner) = weak.upgrade() {
+                if matches!(inner.state.get_untracked(), WsState::Reconnecting { .. }) {
+                    connect_socket(&inner);
+                }
+            }
+        });
+        let window = window();
+        let _ = window.add_event_listener_with_callback("online", on_online.as_ref().unchecked_ref());
+
+        let on_dispose = inner.clone();
+        on_cleanup(move || {
+            let _ = window.remove_event_listener_with_callback("online", on_online.as_ref().unchecked_ref());
+            on_dispose.shutdown();
+        });
+
+        Self { inner, handlers, next_id: Rc::default(), _out: std::marker::PhantomData }
+    }
+
+    pub fn state(&self) -> ReadSignal<WsState> {
+        *self.inner.state
+    }
+
+    // Enviada na hora se conectado; senão vai para a fila.
+    pub fn send(&self, message: &Out) -> Result<(), WsError> {
+        if self.inner.closed.get() {
+            return Err(WsError::Closed);
+        }
+        let text = serde_json::to_string(message).map_err(|e| WsError::Encode(e.to_string()))?;
+        self.inner.send_text(text);
+        Ok(())
+    }
+
+    // Mensagens que não decodificam como `In` são só logadas. O handler sai junto
+    // com o escopo reativo atual.
+    pub fn on_message(&self, handler: impl Fn(&In) + 'static) {
+        self.subscribe(move |message| handler(message));
+    }
+
+    fn subscribe(&self, handler: impl Fn(&Rc<In>) + 'static) {
+        let id = self.next_id.get();
+        self.next_id.set(id.wrapping_add(1));
+        self.handlers.borrow_mut().push((id, Rc::new(handler)));
+        let handlers = Rc::downgrade(&self.handlers);
+        on_cleanup(move || {
+            if let Some(handlers) = handlers.upgrade() {
+                handlers.borrow_mut().retain(|(other, _)| *other != id);
+            }
+        });
+    }
+
+    // A última mensagem recebida, como signal.
+    pub fn latest(&self) -> ReadSignal<Option<Rc<In>>> {
+        let latest = create_signal(None);
+        self.subscribe(move |message| latest.set(Some(message.clone())));
+        *latest
+    }
+
+    pub fn queued(&self) -> usize {
+        self.inner.queue.borrow().len()
+    }
+
+    // Abre uma nova conexão agora, zerando o backoff (também reabre após `close`).
+    pub fn reconnect(&self) {
+        self.inner.closed.set(false);
+        self.inner.attempt.set(0);
+        self.inner.state.set(WsState::Connecting);
+        connect_socket(&self.inner);
+    }
+
+    // Fecha sem reconectar; a fila é mantida para um `reconnect` futuro.
+    pub fn close(&self) {
+        self.inner.shutdown();
+        self.inner.state.set(WsState::Closed);
+    }
+}
+
+// Rodam no navegador contra o eco local (`node scripts/ws-echo-server.mjs`):
+//
+//     CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner \
+//         cargo test --target wasm32-unknown-unknown
+//
+// `WS_ECHO_URL` troca o endereço na compilação.
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use serde_json::{json, Value};
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    const ECHO_URL: &str = match option_env!("WS_ECHO_URL") {
+        Some(url) => url,
+        None => "ws://127.0.0.1:8765",
+    };
+    // Porta sem ninguém escutando: toda conexão falha
+    const DEAD_URL: &str = "ws://127.0.0.1:9";
+
+    type Received = Rc<RefCell<Vec<Value>>>;
+
+    fn connect(url: &str, options: WsOptions) -> (WsClient<Value, Value>, Received) {
+        let received = Received::default();
+        let mut client = None;
+        let _ = create_root(|| {
+            let ws = WsClient::connect(url, options);
+            let sink = received.clone();
+            ws.on_message(move |message: &Value| sink.borrow_mut().push(message.clone()));
+            client = Some(ws);
+        });
+        (client.unwrap(), received)
+    }
+
+    async fn wait_until(what: &str, condition: impl Fn() -> bool) {
+        for _ in 0..100 {
+            if condition() {
+                return;
+            }
+            sleep(20).await;
+        }
+        panic!("tempo esgotado esperando: {}", what);
+    }
+
+    #[wasm_bindgen_test]
+    async fn sends_and_receives() {
+        let (ws, received) = connect(ECHO_URL, WsOptions::default().no_heartbeat());
+        wait_until("abrir", || ws.state().get_untracked().is_open()).await;
+
+        ws.send(&json!({ "n": 1 })).unwrap();
+        wait_until("eco", || !received.borrow().is_empty()).await;
+        assert_eq!(*received.borrow(), vec![json!({ "n": 1 })]);
+        ws.close();
+        assert!(matches!(ws.send(&json!({ "n": 2 })), Err(WsError::Closed)));
+    }
+
+    #[wasm_bindgen_test]
+    async fn queues_until_connected() {
+        let (ws, received) = connect(ECHO_URL, WsOptions::default().no_heartbeat().queue_limit(2));
+        assert_eq!(ws.state().get_untracked(), WsState::Connecting);
+        for n in 1..=3 {
+            ws.send(&json!({ "n": n })).unwrap();
+        }
+        // Acima do limite, a mais antiga sai
+        assert_eq!(ws.queued(), 2);
+
+        wait_until("esvaziar a fila", || received.borrow().len() == 2).await;
+        assert_eq!(ws.queued(), 0);
+        assert_eq!(*received.borrow(), vec![json!({ "n": 2 }), json!({ "n": 3 })]);
+        ws.close();
+    }
+
+    #[wasm_bindgen_test]
+    async fn reconnects_with_backoff_until_max_retries() {
+        let (ws, _) = connect(DEAD_URL, WsOptions::default().no_heartbeat().backoff(10, 20).max_retries(2));
+        let states = Rc::new(RefCell::new(Vec::new()));
+        let _ = create_root({
+            let states = states.clone();
+            let state = ws.state();
+            move || create_effect(move || states.borrow_mut().push(state.get()))
+        });
+
+        wait_until("desistir", || ws.state().get_untracked() == WsState::Closed).await;
+        assert_eq!(
+            *states.borrow(),
+            vec![
+                WsState::Connecting,
+                WsState::Reconnecting { attempt: 1 },
+                WsState::Reconnecting { attempt: 2 },
+                WsState::Closed,
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn reconnects_after_server_close() {
+        let (ws, received) = connect(ECHO_URL, WsOptions::default().no_heartbeat().backoff(10, 20));
+        wait_until("abrir", || ws.state().get_untracked().is_open()).await;
+
+        // O eco derruba a conexão ao receber isto
+        ws.send(&json!({ "type": "close" })).unwrap();
+        wait_until("cair", || matches!(ws.state().get_untracked(), WsState::Reconnecting { attempt: 1 })).await;
+        wait_until("reabrir", || ws.state().get_untracked().is_open()).await;
+
+        ws.send(&json!({ "n": 1 })).unwrap();
+        wait_until("eco", || !received.borrow().is_empty()).await;
+        assert_eq!(*received.borrow(), vec![json!({ "n": 1 })]);
+        ws.close();
+    }
+
+    #[wasm_bindgen_test]
+    async fn heartbeat_replies_do_not_reach_handlers() {
+        // O eco devolve o próprio ping, que conta como resposta e é filtrado
+        let options = WsOptions::default().heartbeat(30, r#"{"type":"ping"}"#, r#"{"type":"pong"}"#);
+        let (ws, received) = connect(ECHO_URL, options);
+        wait_until("abrir", || ws.state().get_untracked().is_open()).await;
+
+        sleep(200).await;
+        assert!(received.borrow().is_empty());
+        assert!(ws.state().get_untracked().is_open());
+
+        ws.send(&json!({ "n": 1 })).unwrap();
+        wait_until("eco", || !received.borrow().is_empty()).await;
+        assert_eq!(*received.borrow(), vec![json!({ "n": 1 })]);
+        ws.close();
+    }
+}
@@ -1,22 +1,22 @@
 mod qt;  // Declarar o módulo
 use qt::*;  // Importar tudo
+use crate::form::{self, Field, Form};
+use crate::tauri_bridge::{self, InvokeError};
 
+use serde::Serialize;
 use std::rc::Rc;
+use sycamore::futures::spawn_local_scoped;
 use sycamore::prelude::*;
-use wasm_bindgen::prelude::*;
 use web_sys::MouseEvent;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlInputElement};
 
 
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
-    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+#[derive(Serialize)]
+struct GreetArgs {
+    name: String,
 }
 
-
-
 #[component]
 pub fn App() -> View {
     // Signals para os widgets
@@ -33,356 +33,398 @@ pub fn App() -> View {
     let combo_index = create_signal(0);
     let status_text = create_signal(String::from("Pronto"));
 
-    let combo_items = vec![
+    let combo_items = create_signal(vec![
         "Bronze".to_string(),
         "Prata".to_string(),
         "Ouro".to_string(),
         "Platina".to_string(),
-    ];
+    ]);
 
     let texto = create_signal(String::new());
 
-    view! {
+    // Validação do formulário de cadastro
+    let cadastro = Form::new();
+    let name_field = cadastro.register(
+        Field::from_signal(name)
+            .validate(form::required("Informe o nome"))
+            .validate(form::min_length(3, "Nome muito curto"))
+    );
+    let email_field = cadastro.register(
+        Field::from_signal(email)
+            .validate(form::required("Informe o e-mail"))
+            .validate(form::email("E-mail inválido"))
+    );
+    let age_field = cadastro.register(Field::from_signal(age).validate(form::range(0, 120)));
+
+    // Folha de estilo aplicada a todos os widgets qt abaixo do provider
+    let qt_style = create_signal(QtStyle::default().with_sheet(r#"
+        PushButton#salvar { font-weight: 600; }
+        PushButton#salvar:hover { border-color: #1e8e3e; }
+        FormRow { font-weight: 500; }
+    "#));
 
-        VBoxLayout {
-
-            HBoxLayout(spacing = 5, margin = 5) {
-                TextEdit(
-                    value = texto.clone(),
-                    placeholder = "Digite seu texto aqui...".to_string(),
-                    height = 150,
-                    line_wrap = true,
-                    readonly = false,
-                    on_change = Rc::new(|novo_texto| {
-                        // Faça algo quando o texto mudar
-                        web_sys::console::log_1(&format!("Texto alterado: {}", novo_texto).into());
-                    })
-                )
+    view! {
+        QtStyleProvider(style = qt_style) {
+
+            VBoxLayout {
+
+                HBoxLayout(spacing = 5, margin = 5) {
+                    TextEdit(
+                        value = texto.clone(),
+                        placeholder = "Digite seu texto aqui...".to_string(),
+                        height = 150,
+                        line_wrap = true,
+                        readonly = false,
+                        on_change = Rc::new(|novo_texto| {
+                            // Faça algo quando o texto mudar
+                            web_sys::console::log_1(&format!("Texto alterado: {}", novo_texto).into());
+                        })
+                    )
+                }
             }
-        }
 
 
-        Slider(
-            value = create_signal(50),
-            min = 0,
-            max = 100,
-            step = 1,
-            show_value = true,
-            on_change = Rc::new(|v| {
-                // Fazer algo com o valor
-            })
-        )
+            Slider(
+                value = create_signal(50),
+                min = 0,
+                max = 100,
+                step = 1,
+                show_value = true,
+                on_change = Rc::new(|v| {
+                    // Fazer algo com o valor
+                })
+            )
     
-        // // Vertical
-        // Slider(
-        //     value = create_signal(75),
-        //     min = 0,
-        //     max = 100,
-        //     step = 5,
-        //     orientation = Orientation::Vertical,
-        //     on_change = Rc::new(|v| {
-        //         // Callback
-        //     })
-        // )
+            // // Vertical
+            // Slider(
+            //     value = create_signal(75),
+            //     min = 0,
+            //     max = 100,
+            //     step = 5,
+            //     orientation = Orientation::Vertical,
+            //     on_change = Rc::new(|v| {
+            //         // Callback
+            //     })
+            // )
             
-        div(style = "padding: 20px; font-family: 'Segoe UI', Arial, sans-serif;") {
-            h1(style = "color: #333; margin-bottom: 20px;") { "Demo Qt Widgets em Sycamore" }
+            div(style = "padding: 20px; font-family: 'Segoe UI', Arial, sans-serif;") {
+                h1(style = "color: #333; margin-bottom: 20px;") { "Demo Qt Widgets em Sycamore" }
             
-            // Seção 1: FormLayout
-            VBoxLayout(spacing = 20, margin = 10) {
+                // Seção 1: FormLayout
+                VBoxLayout(spacing = 20, margin = 10) {
                 
-                // Formulário com FormLayout
-                div(style = "border: 1px solid #ccc; padding: 15px; border-radius: 5px; background: #f9f9f9;") {
-                    h2(style = "margin-top: 0; color: #555;") { "Formulário de Cadastro" }
+                    // Formulário com FormLayout
+                    div(style = "border: 1px solid #ccc; padding: 15px; border-radius: 5px; background: #f9f9f9;") {
+                        h2(style = "margin-top: 0; color: #555;") { "Formulário de Cadastro" }
                     
-                    FormLayout(spacing = 10, margin = 10, label_spacing = 15) {
-                        FormRow(label = "Nome:".to_string()) {
-                            LineEdit(
-                                value = name.clone(),
-                                placeholder = "Digite seu nome completo".to_string(),
-                                on_input = Rc::new({
-                                    let name = name.clone();
-                                    move |v| name.set(v)
-                                })
-                            )
-                        }
+                        FormLayout(spacing = 10, margin = 10, label_spacing = 15) {
+                            FormRow(label = "Nome:".to_string(), error = name_field.error) {
+                                LineEdit(
+                                    value = name.clone(),
+                                    placeholder = "Digite seu nome completo".to_string(),
+                                    error = name_field.error,
+                                    touched = name_field.touched,
+                                    on_input = Rc::new({
+                                        let name = name.clone();
+                                        move |v| name.set(v)
+                                    })
+                                )
+                            }
                         
-                        FormRow(label = "E-mail:".to_string()) {
-                            LineEdit(
-                                value = email.clone(),
-                                placeholder = "seu@email.com".to_string(),
-                                on_input = Rc::new({
-                                    let email = email.clone();
-                                    move |v| email.set(v)
-                                })
-                            )
-                        }
+                            FormRow(label = "E-mail:".to_string(), error = email_field.error) {
+                                LineEdit(
+                                    value = email.clone(),
+                                    placeholder = "seu@email.com".to_string(),
+                                    error = email_field.error,
+                                    touched = email_field.touched,
+                                    on_input = Rc::new({
+                                        let email = email.clone();
+                                        move |v| email.set(v)
+                                    })
+                                )
+                            }
                         
-                        FormRow(label = "Idade:".to_string()) {
-                            SpinBox(
-                                value = age.clone(),
-                                min = 0,
-                                max = 120,
-                                step = 1,
-                                on_change = Rc::new({
-                                    let age = age.clone();
-                                    move |v| age.set(v)
-                                })
-                            )
-                        }
+                            FormRow(label = "Idade:".to_string(), error = age_field.error) {
+                                SpinBox(
+                                    value = age.clone(),
+                                    min = 0,
+                                    max = 120,
+                                    step = 1,
+                                    clamp = false,
+                                    error = age_field.error,
+                                    touched = age_field.touched,
+                                    on_change = Rc::new({
+                                        let age = age.clone();
+                                        move |v| age.set(v)
+                                    })
+                                )
+                            }
                         
-                        FormRow(label = "Altura (m):".to_string()) {
-                            DoubleSpinBox(
-                                value = height.clone(),
-                                min = 0.5,
-                                max = 2.5,
-                                step = 0.01,
-                                decimals = 2,
-                                on_change = Rc::new({
-                                    let height = height.clone();
-                                    move |v| height.set(v)
-                                })
-                            )
-                        }
+                            FormRow(label = "Altura (m):".to_string()) {
+                                DoubleSpinBox(
+                                    value = height.clone(),
+                                    min = 0.5,
+                                    max = 2.5,
+                                    step = 0.01,
+                                    decimals = 2,
+                                    on_change = Rc::new({
+                                        let height = height.clone();
+                                        move |v| height.set(v)
+                                    })
+                                )
+                            }
                         
-                        FormRow(label = "Peso (kg):".to_string()) {
-                            DoubleSpinBox(
-                                value = weight.clone(),
-                                min = 30.0,
-                                max = 200.0,
-                                step = 0.5,
-                                decimals = 1,
-                                on_change = Rc::new({
-                                    let weight = weight.clone();
-                                    move |v| weight.set(v)
-                                })
-                            )
-                        }
+                            FormRow(label = "Peso (kg):".to_string()) {
+                                DoubleSpinBox(
+                                    value = weight.clone(),
+                                    min = 30.0,
+                                    max = 200.0,
+                                    step = 0.5,
+                                    decimals = 1,
+                                    on_change = Rc::new({
+                                        let weight = weight.clone();
+                                        move |v| weight.set(v)
+                                    })
+                                )
+                            }
                         
-                        FormRow(label = "Categoria:".to_string()) {
-                            // ComboBox(
-                            //     items = combo_items,
-                            //     current_index = combo_index.clone(),
-                            //     on_change = Rc::new({
-                            //         let combo_index = combo_index.clone();
-                            //         move |idx, _value| combo_index.set(idx)
-                            //     })
-                            // )
+                            FormRow(label = "Categoria:".to_string()) {
+                                ComboBox(
+                                    items = combo_items,
+                                    current_index = combo_index,
+                                    separators = create_signal(vec![2]),
+                                    on_change = Rc::new(move |_idx, value| {
+                                        status_text.set(format!("Categoria: {}", value))
+                                    })
+                                )
+                            }
                         }
                     }
-                }
                 
-                // Seção 2: RadioButtons em VBox
-                div(style = "border: 1px solid #ccc; padding: 15px; border-radius: 5px; background: #f9f9f9;") {
-                    h2(style = "margin-top: 0; color: #555;") { "Escolha uma opção" }
+                    // Seção 2: RadioButtons em VBox
+                    div(style = "border: 1px solid #ccc; padding: 15px; border-radius: 5px; background: #f9f9f9;") {
+                        h2(style = "margin-top: 0; color: #555;") { "Escolha uma opção" }
                     
-                    VBoxLayout(spacing = 8, margin = 5) {
-                        RadioButton(
-                            text = "Opção 1 - Iniciante".to_string(),
-                            name = "nivel".to_string(),
-                            checked = radio1.clone(),
-                            on_toggle = Rc::new({
-                                let radio1 = radio1.clone();
-                                let radio2 = radio2.clone();
-                                let radio3 = radio3.clone();
-                                move |v| {
-                                    if v {
-                                        radio1.set(true);
-                                        radio2.set(false);
-                                        radio3.set(false);
+                        VBoxLayout(spacing = 8, margin = 5) {
+                            RadioButton(
+                                text = "Opção 1 - Iniciante".to_string(),
+                                name = "nivel".to_string(),
+                                checked = radio1.clone(),
+                                on_toggle = Rc::new({
+                                    let radio1 = radio1.clone();
+                                    let radio2 = radio2.clone();
+                                    let radio3 = radio3.clone();
+                                    move |v| {
+                                        if v {
+                                            radio1.set(true);
+                                            radio2.set(false);
+                                            radio3.set(false);
+                                        }
                                     }
-                                }
-                            })
-                        )
+                                })
+                            )
                         
-                        RadioButton(
-                            text = "Opção 2 - Intermediário".to_string(),
-                            name = "nivel".to_string(),
-                            checked = radio2.clone(),
-                            on_toggle = Rc::new({
-                                let radio1 = radio1.clone();
-                                let radio2 = radio2.clone();
-                                let radio3 = radio3.clone();
-                                move |v| {
-                                    if v {
-                                        radio1.set(false);
-                                        radio2.set(true);
-                                        radio3.set(false);
+                            RadioButton(
+                                text = "Opção 2 - Intermediário".to_string(),
+                                name = "nivel".to_string(),
+                                checked = radio2.clone(),
+                                on_toggle = Rc::new({
+                                    let radio1 = radio1.clone();
+                                    let radio2 = radio2.clone();
+                                    let radio3 = radio3.clone();
+                                    move |v| {
+                                        if v {
+                                            radio1.set(false);
+                                            radio2.set(true);
+                                            radio3.set(false);
+                                        }
                                     }
-                                }
-                            })
-                        )
+                                })
+                            )
                         
-                        RadioButton(
-                            text = "Opção 3 - Avançado".to_string(),
-                            name = "nivel".to_string(),
-                            checked = radio3.clone(),
-                            on_toggle = Rc::new({
-                                let radio1 = radio1.clone();
-                                let radio2 = radio2.clone();
-                                let radio3 = radio3.clone();
-                                move |v| {
-                                    if v {
-                                        radio1.set(false);
-                                        radio2.set(false);
-                                        radio3.set(true);
+                            RadioButton(
+                                text = "Opção 3 - Avançado".to_string(),
+                                name = "nivel".to_string(),
+                                checked = radio3.clone(),
+                                on_toggle = Rc::new({
+                                    let radio1 = radio1.clone();
+                                    let radio2 = radio2.clone();
+                                    let radio3 = radio3.clone();
+                                    move |v| {
+                                        if v {
+                                            radio1.set(false);
+                                            radio2.set(false);
+                                            radio3.set(true);
+                                        }
                                     }
-                                }
-                            })
-                        )
+                                })
+                            )
+                        }
                     }
-                }
                 
-                // Seção 3: GridLayout com botões
-                div(style = "border: 1px solid #ccc; padding: 15px; border-radius: 5px; background: #f9f9f9;") {
-                    h2(style = "margin-top: 0; color: #555;") { "Grid de Ações" }
+                    // Seção 3: GridLayout com botões
+                    div(style = "border: 1px solid #ccc; padding: 15px; border-radius: 5px; background: #f9f9f9;") {
+                        h2(style = "margin-top: 0; color: #555;") { "Grid de Ações" }
                     
-                    GridLayout(columns = 3, spacing = 8, margin = 5) {
-                        PushButton(
-                            text = "Ação 1".to_string(),
-                            on_click = Rc::new({
-                                let status_text = status_text.clone();
-                                move |_| status_text.set("Ação 1 executada!".to_string())
-                            })
-                        )
+                        GridLayout(columns = 3, spacing = 8, margin = 5) {
+                            PushButton(
+                                text = "Ação 1".to_string(),
+                                on_click = Rc::new({
+                                    let status_text = status_text.clone();
+                                    move |_| status_text.set("Ação 1 executada!".to_string())
+                                })
+                            )
                         
-                        PushButton(
-                            text = "Ação 2".to_string(),
-                            on_click = Rc::new({
-                                let status_text = status_text.clone();
-                                move |_| status_text.set("Ação 2 executada!".to_string())
-                            })
-                        )
+                            PushButton(
+                                text = "Ação 2".to_string(),
+                                on_click = Rc::new({
+                                    let status_text = status_text.clone();
+                                    move |_| status_text.set("Ação 2 executada!".to_string())
+                                })
+                            )
                         
-                        PushButton(
-                            text = "Ação 3".to_string(),
-                            on_click = Rc::new({
-                                let status_text = status_text.clone();
-                                move |_| status_text.set("Ação 3 executada!".to_string())
-                            })
-                        )
+                            PushButton(
+                                text = "Ação 3".to_string(),
+                                on_click = Rc::new({
+                                    let status_text = status_text.clone();
+                                    move |_| status_text.set("Ação 3 executada!".to_string())
+                                })
+                            )
                         
-                        PushButton(
-                            text = "Ação 4".to_string(),
-                            on_click = Rc::new({
-                                let status_text = status_text.clone();
-                                move |_| status_text.set("Ação 4 executada!".to_string())
-                            })
-                        )
+                            PushButton(
+                                text = "Ação 4".to_string(),
+                                on_click = Rc::new({
+                                    let status_text = status_text.clone();
+                                    move |_| status_text.set("Ação 4 executada!".to_string())
+                                })
+                            )
                         
-                        PushButton(
-                            text = "Ação 5".to_string(),
-                            on_click = Rc::new({
-                                let status_text = status_text.clone();
-                                move |_| status_text.set("Ação 5 executada!".to_string())
-                            })
-                        )
+                            PushButton(
+                                text = "Ação 5".to_string(),
+                                on_click = Rc::new({
+                                    let status_text = status_text.clone();
+                                    move |_| status_text.set("Ação 5 executada!".to_string())
+                                })
+                            )
                         
-                        PushButton(
-                            text = "Ação 6".to_string(),
-                            on_click = Rc::new({
-                                let status_text = status_text.clone();
-                                move |_| status_text.set("Ação 6 executada!".to_string())
-                            })
-                        )
+                            PushButton(
+                                text = "Ação 6".to_string(),
+                                on_click = Rc::new({
+                                    let status_text = status_text.clone();
+                                    move |_| status_text.set("Ação 6 executada!".to_string())
+                                })
+                            )
+                        }
                     }
-                }
                 
-                // Seção 4: HBoxLayout com botões principais
-                div(style = "border: 1px solid #ccc; padding: 15px; border-radius: 5px; background: #f9f9f9;") {
-                    h2(style = "margin-top: 0; color: #555;") { "Ações Principais" }
+                    // Seção 4: HBoxLayout com botões principais
+                    div(style = "border: 1px solid #ccc; padding: 15px; border-radius: 5px; background: #f9f9f9;") {
+                        h2(style = "margin-top: 0; color: #555;") { "Ações Principais" }
                     
-                    HBoxLayout(spacing = 10, margin = 5, align = "center".to_string()) {
-                        PushButton(
-                            text = "Salvar".to_string(),
-                            on_click = Rc::new({
-                                let status_text = status_text.clone();
-                                let name = name.clone();
-                                move |_: MouseEvent| {
-                                    status_text.set(format!("Dados de {} salvos!", name.get_clone()))
-                                }
-                            })
-                        )
+                        HBoxLayout(spacing = 10, margin = 5, align = "center".to_string()) {
+                            PushButton(
+                                text = "Salvar".to_string(),
+                                object_name = "salvar".to_string(),
+                                on_click = Rc::new({
+                                    let status_text = status_text.clone();
+                                    let name = name.clone();
+                                    move |_: MouseEvent| {
+                                        spawn_local_scoped(async move {
+                                            let valid = cadastro.submit(|| async move {
+                                                let args = GreetArgs { name: name.get_clone() };
+                                                match tauri_bridge::invoke::<_, String>("greet", &args).await {
+                                                    Ok(greeting) => status_text.set(greeting),
+                                                    // No navegador não há backend para cumprimentar
+                                                    Err(InvokeError::Unavailable) => {
+                                                        status_text.set(format!("Dados de {} salvos!", name.get_clone()))
+                                                    }
+                                                    Err(e) => status_text.set(format!("Erro: {}", e)),
+                                                }
+                                            }).await;
+                                            if !valid {
+                                                status_text.set("Corrija os campos destacados".to_string());
+                                            }
+                                        });
+                                    }
+                                })
+                            )
                         
-                        PushButton(
-                            text = "Cancelar".to_string(),
-                            on_click = Rc::new({
-                                let status_text = status_text.clone();
-                                move |_| status_text.set("Operação cancelada".to_string())
-                            })
-                        )
+                            PushButton(
+                                text = "Cancelar".to_string(),
+                                on_click = Rc::new({
+                                    let status_text = status_text.clone();
+                                    move |_| status_text.set("Operação cancelada".to_string())
+                                })
+                            )
                         
-                        PushButton(
-                            text = "Limpar".to_string(),
-                            on_click = Rc::new({
-                                let name = name.clone();
-                                let email = email.clone();
-                                let age = age.clone();
-                                let status_text = status_text.clone();
-                                move |_| {
-                                    name.set(String::new());
-                                    email.set(String::new());
-                                    age.set(18);
-                                    status_text.set("Formulário limpo".to_string());
-                                }
-                            })
-                        )
+                            PushButton(
+                                text = "Limpar".to_string(),
+                                on_click = Rc::new({
+                                    let status_text = status_text.clone();
+                                    move |_| {
+                                        cadastro.reset();
+                                        age.set(18);
+                                        status_text.set("Formulário limpo".to_string());
+                                    }
+                                })
+                            )
+                        }
                     }
-                }
                 
-                // Barra de status
-                div(style = "padding: 10px; background: #e8e8e8; border-radius: 5px; border: 1px solid #ccc;") {
-                    RLabel(
-                        text = create_signal(format!("Status: {}", status_text.get_clone())),
-                        for_id = String::new()
-                    )
-                }
-                
-                // Resumo dos dados
-                div(style = "border: 1px solid #ccc; padding: 15px; border-radius: 5px; background: #f0f8ff;") {
-                    h2(style = "margin-top: 0; color: #555;") { "Resumo dos Dados" }
-                    
-                    VBoxLayout(spacing = 5, margin = 5) {
+                    // Barra de status
+                    div(style = "padding: 10px; background: #e8e8e8; border-radius: 5px; border: 1px solid #ccc;") {
                         RLabel(
-                            text = create_signal(format!("Nome: {}", name.get_clone())),
+                            text = create_signal(format!("Status: {}", status_text.get_clone())),
                             for_id = String::new()
                         )
+                    }
+                
+                    // Resumo dos dados
+                    div(style = "border: 1px solid #ccc; padding: 15px; border-radius: 5px; background: #f0f8ff;") {
+                        h2(style = "margin-top: 0; color: #555;") { "Resumo dos Dados" }
+                    
+                        VBoxLayout(spacing = 5, margin = 5) {
+                            RLabel(
+                                text = create_signal(format!("Nome: {}", name.get_clone())),
+                                for_id = String::new()
+                            )
                         
-                        RLabel(
-                            text = create_signal(format!("E-mail: {}", email.get_clone())),
-                            for_id = String::new()
-                        )
+                            RLabel(
+                                text = create_signal(format!("E-mail: {}", email.get_clone())),
+                                for_id = String::new()
+                            )
                         
-                        RLabel(
-                            text = create_signal(format!("Idade: {} anos", age.get())),
-                            for_id = String::new()
-                        )
+                            RLabel(
+                                text = create_signal(format!("Idade: {} anos", age.get())),
+                                for_id = String::new()
+                            )
                         
-                        RLabel(
-                            text = create_signal(format!("Altura: {:.2} m", height.get())),
-                            for_id = String::new()
-                        )
+                            RLabel(
+                                text = create_signal(format!("Altura: {:.2} m", height.get())),
+                                for_id = String::new()
+                            )
                         
-                        RLabel(
-                            text = create_signal(format!("Peso: {:.1} kg", weight.get())),
-                            for_id = String::new()
-                        )
+                            RLabel(
+                                text = create_signal(format!("Peso: {:.1} kg", weight.get())),
+                                for_id = String::new()
+                            )
                         
-                        RLabel(
-                            text = create_signal(format!("IMC: {:.2}", weight.get() / (height.get() * height.get()))),
-                            for_id = String::new()
-                        )
+                            RLabel(
+                                text = create_signal(format!("IMC: {:.2}", weight.get() / (height.get() * height.get()))),
+                                for_id = String::new()
+                            )
                         
-                        RLabel(
-                            text = create_signal(format!(
-                                "Nível: {}",
-                                if radio1.get() { "Iniciante" }
-                                else if radio2.get() { "Intermediário" }
-                                else if radio3.get() { "Avançado" }
-                                else { "Não selecionado" }
-                            )),
-                            for_id = String::new()
-                        )
+                            RLabel(
+                                text = create_signal(format!(
+                                    "Nível: {}",
+                                    if radio1.get() { "Iniciante" }
+                                    else if radio2.get() { "Intermediário" }
+                                    else if radio3.get() { "Avançado" }
+                                    else { "Não selecionado" }
+                                )),
+                                for_id = String::new()
+                            )
+                        }
                     }
                 }
             }
@@ -1,201 +1,390 @@
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, Ordering};
 use sycamore::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{Event, HtmlInputElement};
-use rand;
+use web_sys::{Event, HtmlInputElement, KeyboardEvent, MouseEvent};
+use super::style::{use_qt_style, QtState};
+
+// Ids únicos por instância, sem depender de `rand`
+static NEXT_COMBO_ID: AtomicU32 = AtomicU32::new(1);
+
+// Tempo máximo entre teclas para continuar a mesma busca (como no Qt)
+const TYPE_AHEAD_MS: f64 = 700.0;
 
 #[derive(Props)]
 pub struct ComboBoxProps {
-    pub items: Vec<String>,
+    pub items: Signal<Vec<String>>,
+
+    // -1 = nenhum item selecionado
     #[prop(default)]
     pub current_index: Signal<i32>,
+
+    // Ícone (src de imagem) por índice; pode ser mais curto que `items`
+    #[prop(default)]
+    pub icons: Signal<Vec<Option<String>>>,
+
+    // Desenha um separador antes do item de cada índice listado
+    #[prop(default)]
+    pub separators: Signal<Vec<usize>>,
+
     #[prop(default)]
     pub disabled: bool,
+
     #[prop(default)]
     pub editable: bool,
+
+    #[prop(default)]
+    pub object_name: String,
+
     pub on_change: Rc<dyn Fn(i32, String)>,
 }
 
+// Próximo item cujo texto começa com `prefix`, a partir de `from` e dando a volta.
+fn find_prefix(items: &[String], prefix: &str, from: usize) -> Option<usize> {
+    let prefix = prefix.to_lowercase();
+    (0..items.len())
+        .map(|offset| (from + offset) % items.len())
+        .find(|&i| items[i].to_lowercase().starts_with(&prefix))
+}
+
 #[component]
 pub fn ComboBox(props: ComboBoxProps) -> View {
+    let items = props.items;
+    let current_index = props.current_index;
+    let icons = props.icons;
+    let separators = props.separators;
+    let disabled = props.disabled;
+    let on_change = props.on_change;
+    let object_name = Rc::new(props.object_name);
+
+    let qt = use_qt_style();
+    let id = NEXT_COMBO_ID.fetch_add(1, Ordering::Relaxed);
     let is_hover = create_signal(false);
     let is_focus = create_signal(false);
-    let custom_value = create_signal(String::new());
-
-    let container_style = r#"
-        position: relative;
-        display: inline-block;
-        width: 200px;
-    "#;
-
-    let select_style = r#"
-        box-sizing: border-box;
-        width: 100%;
-        height: 26px;
-        padding: 4px 24px 4px 6px;
-        background: linear-gradient(to bottom, #ffffff, #f6f6f6);
-        border: 1px solid #8f8f8f;
-        border-radius: 3px;
-        font-family: 'Segoe UI', 'DejaVu Sans', Arial, sans-serif;
-        font-size: 13px;
-        color: #000000;
-        outline: none;
-        cursor: pointer;
-        appearance: none;
-    "#;
-
-    let select_hover_style = format!("{}\n        border-color: #5a8dee;\n        background: linear-gradient(to bottom, #ffffff, #fafafa);", select_style);
-
-    let select_focus_style = format!("{}\n        border-color: #377af5;\n        box-shadow: inset 0 0 0 1px rgba(55,122,245,0.6);", select_style);
-
-    let select_disabled_style = format!("{}\n        background: #efefef;\n        color: #7f7f7f;\n        border-color: #bfbfbf;\n        cursor: default;", select_style);
-
-    let arrow_style = r#"
-        position: absolute;
-        right: 6px;
-        top: 50%;
-        transform: translateY(-50%);
-        width: 0;
-        height: 0;
-        border-left: 4px solid transparent;
-        border-right: 4px solid transparent;
-        border-top: 5px solid #000000;
-        pointer-events: none;
-    "#;
-
-    let arrow_disabled_style = format!("{}\n        border-top-color: #7f7f7f;", arrow_style);
-
-    let input_style = r#"
-        box-sizing: border-box;
-        width: 100%;
-        height: 26px;
-        padding: 4px 24px 4px 6px;
-        background: #ffffff;
-        border: 1px solid #8f8f8f;
-        border-radius: 3px;
-        font-family: 'Segoe UI', 'DejaVu Sans', Arial, sans-serif;
-        font-size: 13px;
-        color: #000000;
-        outline: none;
-    "#;
-
-    let input_hover_style = format!("{}\n        border-color: #5a8dee;", input_style);
-
-    let input_focus_style = format!("{}\n        border-color: #377af5;\n        box-shadow: inset 0 0 0 1px rgba(55,122,245,0.6);", input_style);
-
-    let on_change = props.on_change.clone();
+
+    // Itens removidos podem deixar a seleção apontando para fora da lista
+    create_effect(move || {
+        let len = items.with(|i| i.len()) as i32;
+        if current_index.get_untracked() >= len {
+            current_index.set(len - 1);
+        }
+    });
+
+    let select = Rc::new({
+        let on_change = on_change.clone();
+        move |index: usize| {
+            let Some(text) = items.with_untracked(|i| i.get(index).cloned()) else { return };
+            current_index.set(index as i32);
+            on_change(index as i32, text);
+        }
+    });
+
+    let state = move || {
+        if disabled {
+            QtState::Disabled
+        } else if is_focus.get() {
+            QtState::Focus
+        } else if is_hover.get() {
+            QtState::Hover
+        } else {
+            QtState::Normal
+        }
+    };
+
+    let arrow_style = move || qt.with(|qt| format!(
+        "position: absolute; right: 6px; top: 50%; transform: translateY(-50%); width: 0; height: 0; \
+         border-left: 4px solid transparent; border-right: 4px solid transparent; \
+         border-top: 5px solid {}; pointer-events: none;",
+        if disabled { &qt.palette.disabled_text } else { &qt.palette.text }
+    ));
+
+    // --- EDITÁVEL ---
 
     if props.editable {
-        let on_change_input = on_change.clone();
-        let datalist_id = format!("combo-datalist-{}", rand::random::<u32>());
-        
-        view! {
-            div(style = container_style) {
+        let datalist_id = format!("combo-{}-list", id);
+        let list_id = datalist_id.clone();
+        // Texto livre digitado que não corresponde a nenhum item; fora isso, o campo
+        // mostra o item de `current_index`, acompanhando mudanças de fora
+        let custom_value = create_signal(None::<String>);
+        create_effect(move || {
+            if current_index.get() >= 0 {
+                custom_value.set(None);
+            }
+        });
+        let text = move || custom_value.get_clone().unwrap_or_else(|| {
+            let index = current_index.get();
+            items.with(|i| i.get(index.max(0) as usize).cloned().filter(|_| index >= 0)).unwrap_or_default()
+        });
+        let input_style = {
+            let object_name = object_name.clone();
+            move || {
+                let state = state();
+                qt.with(|qt| {
+                    let base = format!(
+                        "width: 100%; height: {}px; padding: 4px 24px 4px 6px; {}",
+                        qt.metrics.control_height, qt.field(state)
+                    );
+                    qt.apply("ComboBox", &object_name, state, base)
+                })
+            }
+        };
+
+        return view! {
+            div(style = "position: relative; display: inline-block; width: 200px;") {
                 input(
                     r#type = "text",
-                    value = custom_value.get_clone(),
-                    disabled = props.disabled,
-                    list = datalist_id.clone(),
-                    style = move || {
-                        if props.disabled {
-                            select_disabled_style.clone()
-                        } else if is_focus.get() {
-                            input_focus_style.clone()
-                        } else if is_hover.get() {
-                            input_hover_style.clone()
-                        } else {
-                            input_style.to_string()
-                        }
-                    },
+                    prop:value = text,
+                    disabled = disabled,
+                    list = list_id,
+                    style = input_style,
                     on:mouseenter = move |_| is_hover.set(true),
                     on:mouseleave = move |_| is_hover.set(false),
                     on:focus = move |_| is_focus.set(true),
                     on:blur = move |_| is_focus.set(false),
                     on:input = move |e: Event| {
-                        if !props.disabled {
+                        if !disabled {
                             let input = e.target().unwrap().dyn_into::<HtmlInputElement>().unwrap();
                             let value = input.value();
-                            custom_value.set(value.clone());
-                            (on_change_input)(-1, value);
+                            // Texto livre que coincide com um item seleciona esse item
+                            let index = items.with_untracked(|i| i.iter().position(|item| *item == value))
+                                .map(|i| i as i32)
+                                .unwrap_or(-1);
+                            if index < 0 {
+                                custom_value.set(Some(value.clone()));
+                            }
+                            current_index.set(index);
+                            on_change(index, value);
                         }
                     }
                 )
-                
+
                 datalist(id = datalist_id) {
-                    {
-                        View::new_fragment(
-                            props.items.iter().map(|item| {
-                                let item = item.clone();
-                                view! {
-                                    option(value = item)
-                                }
-                            }).collect::<Vec<_>>()
-                        )
-                    }
+                    Indexed(
+                        list = items,
+                        view = |item| view! { option(value = item) }
+                    )
                 }
-                
-                div(
-                    style = if props.disabled {
-                        arrow_disabled_style.as_str()
-                    } else {
-                        arrow_style
+
+                div(style = arrow_style)
+            }
+        };
+    }
+
+    // --- POPUP ---
+
+    let open = create_signal(false);
+    let highlighted = create_signal(-1i32);
+    let search = create_signal((String::new(), 0.0f64));
+
+    let open_popup = move || {
+        if !disabled {
+            highlighted.set(current_index.get_untracked());
+            open.set(true);
+        }
+    };
+
+    let type_ahead = {
+        let select = select.clone();
+        move |ch: String| {
+            let now = js_sys::Date::now();
+            let (mut buffer, last) = search.get_clone_untracked();
+            if now - last > TYPE_AHEAD_MS {
+                buffer.clear();
+            }
+            buffer.push_str(&ch);
+            search.set((buffer.clone(), now));
+
+            let current = if open.get_untracked() { highlighted.get_untracked() } else { current_index.get_untracked() };
+            // Repetir a mesma letra percorre os itens que começam com ela
+            let repeated = buffer.chars().count() > 1 && buffer.chars().all(|c| buffer.starts_with(c));
+            let (prefix, from) = if repeated || buffer.chars().count() == 1 {
+                (ch, (current + 1).max(0) as usize)
+            } else {
+                (buffer, current.max(0) as usize)
+            };
+            let found = items.with_untracked(|i| find_prefix(i, &prefix, from));
+            if let Some(index) = found {
+                if open.get_untracked() {
+                    highlighted.set(index as i32);
+                } else {
+                    select(index);
+                }
+            }
+        }
+    };
+
+    let on_keydown = {
+        let select = select.clone();
+        move |e: KeyboardEvent| {
+            if disabled {
+                return;
+            }
+            let len = items.with_untracked(|i| i.len()) as i32;
+            let is_open = open.get_untracked();
+            let step = |delta: i32| {
+                if len == 0 {
+                    return;
+                }
+                let from = if is_open { highlighted.get_untracked() } else { current_index.get_untracked() };
+                let next = (from + delta).clamp(0, len - 1);
+                if is_open { highlighted.set(next) } else { select(next as usize) }
+            };
+            match e.key().as_str() {
+                "ArrowDown" if e.alt_key() => open_popup(),
+                "ArrowDown" => step(1),
+                "ArrowUp" => step(-1),
+                "Home" => step(-len),
+                "End" => step(len),
+                "Enter" | " " if is_open => {
+                    let index = highlighted.get_untracked();
+                    if index >= 0 {
+                        select(index as usize);
                     }
-                )
+                    open.set(false);
+                }
+                "Enter" | " " | "F4" => open_popup(),
+                "Escape" if is_open => open.set(false),
+                key if key.chars().count() == 1 && !e.ctrl_key() && !e.meta_key() => {
+                    type_ahead(key.to_string());
+                }
+                _ => return,
             }
+            e.prevent_default();
         }
-    } else {
+    };
+
+    let field_style = {
+        let object_name = object_name.clone();
+        move || {
+            let state = if open.get() { QtState::Pressed } else { state() };
+            qt.with(|qt| {
+                let base = format!(
+                    "box-sizing: border-box; width: 100%; height: {}px; padding: 4px 24px 4px 6px; \
+                     border-radius: {}px; display: flex; align-items: center; gap: 6px; \
+                     white-space: nowrap; overflow: hidden; {}",
+                    qt.metrics.control_height, qt.metrics.radius, qt.button(state)
+                );
+                qt.apply("ComboBox", &object_name, state, base)
+            })
+        }
+    };
+
+    let popup_style = move || qt.with(|qt| format!(
+        "position: absolute; left: 0; right: 0; top: calc(100% + 2px); z-index: 1000; \
+         max-height: 240px; overflow-y: auto; padding: 2px 0; margin: 0; list-style: none; \
+         background: {}; border: 1px solid {}; border-radius: {}px; \
+         box-shadow: 0 4px 12px rgba(0,0,0,0.18); {}",
+        qt.palette.base, qt.palette.border, qt.metrics.radius, qt.font()
+    ));
+
+    let icon_view = |src: Option<String>| match src {
+        Some(src) => view! { img(src = src, style = "width: 16px; height: 16px; flex-shrink: 0;") },
+        None => view! {},
+    };
+
+    let current_view = move || {
+        let index = current_index.get();
+        let text = items.with(|i| i.get(index.max(0) as usize).cloned().filter(|_| index >= 0)).unwrap_or_default();
+        let icon = icons.with(|i| i.get(index.max(0) as usize).cloned().flatten().filter(|_| index >= 0));
+        let icon = icon_view(icon);
         view! {
-            div(style = container_style) {
-                select(
-                    disabled = props.disabled,
-                    style = move || {
-                        if props.disabled {
-                            select_disabled_style.clone()
-                        } else if is_focus.get() {
-                            select_focus_style.clone()
-                        } else if is_hover.get() {
-                            select_hover_style.clone()
-                        } else {
-                            select_style.to_string()
-                        }
-                    },
-                    on:mouseenter = move |_| is_hover.set(true),
-                    on:mouseleave = move |_| is_hover.set(false),
-                    on:focus = move |_| is_focus.set(true),
-                    on:blur = move |_| is_focus.set(false),
-                    on:change = move |e: Event| {
-                        if !props.disabled {
-                            let select = e.target().unwrap().dyn_into::<web_sys::HtmlSelectElement>().unwrap();
-                            let index = select.selected_index();
-                            let value = select.value();
-                            (on_change)(index, value);
-                        }
-                    }
-                ) {
-                    {
-                        View::new_fragment(
-                            props.items.iter().enumerate().map(|(i, item)| {
-                                let item = item.clone();
-                                let i = i as i32;
-                                let selected = props.current_index.get() == i;
-                                view! {
-                                    option(value = item.clone(), selected = selected) {
-                                        (item)
-                                    }
-                                }
-                            }).collect::<Vec<_>>()
-                        )
+            (icon)
+            span(style = "overflow: hidden; text-overflow: ellipsis;") { (text) }
+        }
+    };
+
+    let rows = {
+        let select = select.clone();
+        move || {
+            let entries = items.with(|i| i.clone());
+            let row_icons = icons.get_clone();
+            let separators = separators.get_clone();
+            entries.into_iter().enumerate().map(|(index, text)| {
+                let select = select.clone();
+                let icon = icon_view(row_icons.get(index).cloned().flatten());
+                let separator = if index > 0 && separators.contains(&index) {
+                    view! {
+                        li(role = "separator", style = move || qt.with(|qt| format!(
+                            "height: 1px; margin: 3px 0; background: {};", qt.palette.disabled_border
+                        )))
                     }
-                }
-                
-                div(
-                    style = if props.disabled {
-                        arrow_disabled_style.as_str()
+                } else {
+                    view! {}
+                };
+                let row_style = move || qt.with(|qt| {
+                    let p = &qt.palette;
+                    let (background, color) = if highlighted.get() == index as i32 {
+                        (p.highlight.as_str(), p.base.as_str())
                     } else {
-                        arrow_style
+                        ("transparent", p.text.as_str())
+                    };
+                    let weight = if current_index.get() == index as i32 { "font-weight: 600;" } else { "" };
+                    format!(
+                        "display: flex; align-items: center; gap: 6px; padding: 3px 8px; cursor: default; \
+                         background: {}; color: {}; {}",
+                        background, color, weight
+                    )
+                });
+                view! {
+                    (separator)
+                    li(
+                        role = "option",
+                        aria-selected = move || (current_index.get() == index as i32).to_string(),
+                        style = row_style,
+                        on:mouseenter = move |_| highlighted.set(index as i32),
+                        // Evita tirar o foco do combo (o blur fecharia o popup antes do clique)
+                        on:mousedown = |e: MouseEvent| e.prevent_default(),
+                        on:click = move |_| {
+                            select(index);
+                            open.set(false);
+                        }
+                    ) {
+                        (icon)
+                        (text)
                     }
-                )
+                }
+            }).collect::<Vec<_>>()
+        }
+    };
+
+    let popup_id = format!("combo-{}-popup", id);
+    let controls_id = popup_id.clone();
+
+    view! {
+        div(style = "position: relative; display: inline-block; width: 200px;") {
+            div(
+                role = "combobox",
+                tabindex = if disabled { "-1" } else { "0" },
+                aria-expanded = move || open.get().to_string(),
+                aria-controls = controls_id,
+                aria-disabled = disabled.to_string(),
+                style = field_style,
+                on:mouseenter = move |_| is_hover.set(true),
+                on:mouseleave = move |_| is_hover.set(false),
+                on:focus = move |_| is_focus.set(true),
+                on:blur = move |_| {
+                    is_focus.set(false);
+                    open.set(false);
+                },
+                on:keydown = on_keydown,
+                on:click = move |_| if open.get_untracked() { open.set(false) } else { open_popup() }
+            ) {
+                (current_view)
             }
+
+            div(style = arrow_style)
+
+            (move || if open.get() {
+                let rows = rows();
+                let popup_id = popup_id.clone();
+                view! {
+                    ul(id = popup_id, role = "listbox", style = popup_style) {
+                        (rows)
+                    }
+                }
+            } else {
+                view! {}
+            })
         }
     }
-}
\ No newline at end of file
+}
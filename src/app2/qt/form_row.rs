@@ -4,6 +4,7 @@ use wasm_bindgen::prelude::*;
 use web_sys::MouseEvent;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlInputElement};
+use super::style::{use_qt_style, QtState};
 
 
 
@@ -11,22 +12,36 @@ use web_sys::{Event, HtmlInputElement};
 pub struct FormRowProps {
     pub label: String,
     children: Children,
+    #[prop(default)]
+    pub error: Signal<Option<String>>,
+    #[prop(default)]
+    pub object_name: String,
 }
 
 #[component]
 pub fn FormRow(props: FormRowProps) -> View {
-    let label_style = r#"
-        font-family: 'Segoe UI', 'DejaVu Sans', Arial, sans-serif;
-        font-size: 13px;
-        color: #000000;
-        text-align: right;
-        padding-right: 5px;
-        white-space: nowrap;
-    "#.to_string();
+    let qt = use_qt_style();
+    let object_name = props.object_name;
+
+    let label_style = move || qt.with(|qt| {
+        let base = format!("{} text-align: right; padding-right: 5px; white-space: nowrap;", qt.font());
+        qt.apply("FormRow", &object_name, QtState::Normal, base)
+    });
+
+    let error_style = move || qt.with(|qt| format!(
+        "display: block; font-family: {}; font-size: {}px; color: {}; white-space: normal;",
+        qt.fonts.family, qt.metrics.small_font_size, qt.palette.danger
+    ));
+
+    let error = props.error;
 
     view! {
         label(style = label_style) {
             (props.label)
+            (move || match error.get_clone() {
+                Some(message) => view! { span(style = error_style()) { (message) } },
+                None => view! {},
+            })
         }
         div {
             (props.children)
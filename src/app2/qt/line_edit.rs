@@ -4,6 +4,7 @@ use wasm_bindgen::prelude::*;
 use web_sys::MouseEvent;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlInputElement};
+use super::style::{use_qt_style, QtState};
 
 
 
@@ -18,6 +19,16 @@ pub struct LineEditProps {
     #[prop(default)]
     pub disabled: bool,
 
+    #[prop(default)]
+    pub error: Signal<Option<String>>,
+
+    #[prop(default)]
+    pub touched: Signal<bool>,
+
+    // Nome usado nos seletores `LineEdit#nome` da folha de estilo
+    #[prop(default)]
+    pub object_name: String,
+
     pub on_input: Rc<dyn Fn(String)>,
 }
 
@@ -26,36 +37,9 @@ pub fn LineEdit(props: LineEditProps) -> View {
     let is_hover = create_signal(false);
     let is_focus = create_signal(false);
 
-    let base_style = r#"
-        box-sizing: border-box;
-        height: 26px;
-        padding: 4px 6px;
-
-        background: #ffffff;
-        border: 1px solid #8f8f8f;
-        border-radius: 3px;
-
-        font-family: 'Segoe UI', 'DejaVu Sans', Arial, sans-serif;
-        font-size: 13px;
-        color: #000000;
-
-        outline: none;
-    "#.to_string();
-
-    let hover_style = base_style.clone() + r#"
-        border-color: #5a8dee;
-    "#;
-
-    let focus_style = base_style.clone() + r#"
-        border-color: #377af5;
-        box-shadow: inset 0 0 0 1px rgba(55,122,245,0.6);
-    "#;
-
-    let disabled_style = base_style.clone() + r#"
-        background: #efefef;
-        color: #7f7f7f;
-        border-color: #bfbfbf;
-    "#;
+    let qt = use_qt_style();
+    let object_name = props.object_name;
+    let error = props.error;
 
     let on_input = props.on_input.clone();
 
@@ -69,21 +53,33 @@ pub fn LineEdit(props: LineEditProps) -> View {
             disabled = props.disabled,
 
             style = move || {
-                if props.disabled {
-                    disabled_style.clone()
+                let state = if props.disabled {
+                    QtState::Disabled
+                } else if error.with(|e| e.is_some()) {
+                    QtState::Invalid
                 } else if is_focus.get() {
-                    focus_style.clone()
+                    QtState::Focus
                 } else if is_hover.get() {
-                    hover_style.clone()
+                    QtState::Hover
                 } else {
-                    base_style.clone()
-                }
+                    QtState::Normal
+                };
+                qt.with(|qt| {
+                    let base = format!(
+                        "height: {}px; padding: 4px 6px; {}",
+                        qt.metrics.control_height, qt.field(state)
+                    );
+                    qt.apply("LineEdit", &object_name, state, base)
+                })
             },
 
             on:mouseenter = move |_| is_hover.set(true),
             on:mouseleave = move |_| is_hover.set(false),
             on:focus = move |_| is_focus.set(true),
-            on:blur = move |_| is_focus.set(false),
+            on:blur = move |_| {
+                is_focus.set(false);
+                props.touched.set(true);
+            },
 
             on:input = move |e: Event| {
                 let input = e
@@ -12,7 +12,8 @@ pub mod h_box_layout;
 pub mod v_box_layout;
 pub mod slider;
 pub mod text_edit;
-// pub mod combo_box;
+pub mod style;
+pub mod combo_box;
 
 pub use radio_button::*;
 pub use line_edit::*;
@@ -28,6 +29,7 @@ pub use h_box_layout::*;
 pub use v_box_layout::*;
 pub use text_edit::*;
 pub use slider::*;
-// pub use combo_box::*;
+pub use style::*;
+pub use combo_box::*;
 
 
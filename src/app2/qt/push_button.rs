@@ -4,6 +4,7 @@ use wasm_bindgen::prelude::*;
 use web_sys::MouseEvent;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlInputElement};
+use super::style::{use_qt_style, QtState};
 
 
 #[derive(Props)]
@@ -11,6 +12,8 @@ pub struct PushButtonProps {
     pub text: String,
     #[prop(default)]
     pub disabled: bool,
+    #[prop(default)]
+    pub object_name: String,
     pub on_click: Rc<dyn Fn(MouseEvent)>,
 }
 
@@ -19,41 +22,8 @@ pub fn PushButton(props: PushButtonProps) -> View {
     let is_hover = create_signal(false);
     let is_pressed = create_signal(false);
 
-    let base_style = r#"
-        background: linear-gradient(to bottom, #f6f6f6, #dcdcdc);
-        border: 1px solid #8f8f8f;
-        border-radius: 4px;
-        padding: 6px 14px;
-        min-height: 28px;
-
-        font-family: 'Segoe UI', 'DejaVu Sans', Arial, sans-serif;
-        font-size: 13px;
-        color: #000000;
-
-        box-shadow: inset 0 1px 0 rgba(255,255,255,0.8);
-        cursor: pointer;
-        outline: none;
-        user-select: none;
-    "#.to_string();
-
-    let hover_style = base_style.clone() + r#"
-        background: linear-gradient(to bottom, #ffffff, #e6e6e6);
-        border: 1px solid #5a8dee;
-    "#;
-
-    let pressed_style = base_style.clone() + r#"
-        background: linear-gradient(to bottom, #d6d6d6, #f0f0f0);
-        border: 1px solid #5a8dee;
-        box-shadow: inset 0 2px 4px rgba(0,0,0,0.15);
-    "#;
-
-    let disabled_style = base_style.clone() + r#"
-        background: #efefef;
-        border: 1px solid #bfbfbf;
-        color: #7f7f7f;
-        cursor: default;
-        box-shadow: none;
-    "#;
+    let qt = use_qt_style();
+    let object_name = props.object_name;
 
     let on_click = props.on_click.clone();
 
@@ -62,15 +32,22 @@ pub fn PushButton(props: PushButtonProps) -> View {
         button(
             disabled = props.disabled,
             style = move || {
-                if props.disabled {
-                    disabled_style.clone()
+                let state = if props.disabled {
+                    QtState::Disabled
                 } else if is_pressed.get() {
-                    pressed_style.clone()
+                    QtState::Pressed
                 } else if is_hover.get() {
-                    hover_style.clone()
+                    QtState::Hover
                 } else {
-                    base_style.clone()
-                }
+                    QtState::Normal
+                };
+                qt.with(|qt| {
+                    let base = format!(
+                        "border-radius: {}px; padding: 6px 14px; min-height: {}px; {}",
+                        qt.metrics.radius + 1, qt.metrics.control_height + 2, qt.button(state)
+                    );
+                    qt.apply("PushButton", &object_name, state, base)
+                })
             },
             on:mouseenter = move |_| is_hover.set(true),
             on:mouseleave = move |_| {
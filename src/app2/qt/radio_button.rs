@@ -4,6 +4,7 @@ use wasm_bindgen::prelude::*;
 use web_sys::MouseEvent;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlInputElement};
+use super::style::{use_qt_style, QtState};
 
 
 #[derive(Props)]
@@ -19,6 +20,9 @@ pub struct RadioButtonProps {
     #[prop(default)]
     pub name: String,
     
+    #[prop(default)]
+    pub object_name: String,
+    
     pub on_toggle: Rc<dyn Fn(bool)>,
 }
 
@@ -39,54 +43,48 @@ pub fn RadioButton(props: RadioButtonProps) -> View {
         opacity: 0.6;
     "#;
 
-    let radio_style = r#"
-        appearance: none;
-        width: 16px;
-        height: 16px;
-        border: 1px solid #8f8f8f;
-        border-radius: 50%;
-        background: #ffffff;
-        margin: 0;
-        cursor: pointer;
-        outline: none;
-        position: relative;
-        flex-shrink: 0;
-    "#.to_string();
+    let qt = use_qt_style();
+    let object_name = props.object_name;
+    let disabled = props.disabled;
+    let checked = props.checked;
 
-    let radio_hover_style = radio_style.clone() + r#"
-        border-color: #5a8dee;
-    "#;
+    let radio_style = move || {
+        let state = if disabled {
+            QtState::Disabled
+        } else if checked.get() {
+            QtState::Checked
+        } else if is_hover.get() {
+            QtState::Hover
+        } else {
+            QtState::Normal
+        };
+        qt.with(|qt| {
+            let p = &qt.palette;
+            let (background, border) = match state {
+                QtState::Disabled => (&p.disabled_base, &p.disabled_border),
+                QtState::Checked if is_hover.get() => (&p.base, &p.highlight_dark),
+                QtState::Checked => (&p.base, &p.highlight),
+                QtState::Hover => (&p.base, &p.hover),
+                _ => (&p.base, &p.border),
+            };
+            let base = format!(
+                "appearance: none; width: 16px; height: 16px; border: 1px solid {}; border-radius: 50%; \
+                 background: {}; margin: 0; cursor: {}; outline: none; position: relative; flex-shrink: 0;",
+                border, background, if disabled { "default" } else { "pointer" }
+            );
+            qt.apply("RadioButton", &object_name, state, base)
+        })
+    };
 
-    let radio_checked_style = r#"
-        appearance: none;
-        width: 16px;
-        height: 16px;
-        border: 1px solid #377af5;
-        border-radius: 50%;
-        background: #ffffff;
-        margin: 0;
-        cursor: pointer;
-        outline: none;
-        position: relative;
-        flex-shrink: 0;
-    "#.to_string();
+    let indicator_style = move || qt.with(|qt| format!(
+        "position: absolute; width: 8px; height: 8px; background: {}; border-radius: 50%; margin-left: 4px; pointer-events: none;",
+        qt.palette.highlight
+    ));
 
-    let radio_disabled_style = radio_style.clone() + r#"
-        background: #efefef;
-        border-color: #bfbfbf;
-        cursor: default;
-    "#;
-
-    let label_style = r#"
-        margin-left: 6px;
-        font-family: 'Segoe UI', 'DejaVu Sans', Arial, sans-serif;
-        font-size: 13px;
-        color: #000000;
-    "#.to_string();
-
-    let label_disabled_style = label_style.clone() + r#"
-        color: #7f7f7f;
-    "#;
+    let label_style = move || qt.with(|qt| {
+        let color = if disabled { &qt.palette.disabled_text } else { &qt.palette.text };
+        format!("margin-left: 6px; {} color: {};", qt.font(), color)
+    });
 
     let on_toggle = props.on_toggle.clone();
 
@@ -109,21 +107,7 @@ pub fn RadioButton(props: RadioButtonProps) -> View {
                 name = props.name,
                 checked = props.checked,
                 disabled = props.disabled,
-                style = move || {
-                    if props.disabled {
-                        radio_disabled_style.clone()
-                    } else if props.checked.get() {
-                        radio_checked_style.clone() + if is_hover.get() {
-                            "border-color: #2a5fd4;"
-                        } else {
-                            ""
-                        }
-                    } else if is_hover.get() {
-                        radio_hover_style.clone()
-                    } else {
-                        radio_style.clone()
-                    }
-                },
+                style = radio_style,
                 on:change = move |e: Event| {
                     if !props.disabled {
                         let input = e.target().unwrap().dyn_into::<HtmlInputElement>().unwrap();
@@ -136,27 +120,13 @@ pub fn RadioButton(props: RadioButtonProps) -> View {
             // Indicador central quando marcado
             (if props.checked.get() {
                 view! {
-                    span(style = r#"
-                        position: absolute;
-                        width: 8px;
-                        height: 8px;
-                        background: #377af5;
-                        border-radius: 50%;
-                        margin-left: 4px;
-                        pointer-events: none;
-                    "#) {}
+                    span(style = indicator_style()) {}
                 }
             } else {
                 view! {}
             })
             
-            span(
-                style = if props.disabled {
-                    label_disabled_style
-                } else {
-                    label_style
-                }
-            ) {
+            span(style = label_style) {
                 (props.text)
             }
         }
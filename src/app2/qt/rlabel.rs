@@ -4,6 +4,7 @@ use wasm_bindgen::prelude::*;
 use web_sys::MouseEvent;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlInputElement};
+use super::style::{use_qt_style, QtState};
 
 
 #[derive(Props)]
@@ -15,25 +16,17 @@ pub struct RLabelProps {
 
     #[prop(default)]
     pub for_id: String,
+
+    #[prop(default)]
+    pub object_name: String,
 }
 
 
 #[component]
 pub fn RLabel(props: RLabelProps) -> View {
-    let base_style = r#"
-        display: inline-block;
-        margin-right: 6px;
-
-        font-family: 'Segoe UI', 'DejaVu Sans', Arial, sans-serif;
-        font-size: 13px;
-        color: #000000;
-
-        user-select: none;
-    "#.to_string();
-
-    let disabled_style = base_style.clone() + r#"
-        color: #7f7f7f;
-    "#;
+    let qt = use_qt_style();
+    let object_name = props.object_name;
+    let disabled = props.disabled;
 
     let for_attr: Option<String> =
         if props.for_id.is_empty() {
@@ -45,10 +38,16 @@ pub fn RLabel(props: RLabelProps) -> View {
     view! {
         label(
             r#for = for_attr,
-            style = if props.disabled {
-                disabled_style
-            } else {
-                base_style
+            style = move || {
+                let state = if disabled { QtState::Disabled } else { QtState::Normal };
+                qt.with(|qt| {
+                    let color = if disabled { &qt.palette.disabled_text } else { &qt.palette.text };
+                    let base = format!(
+                        "display: inline-block; margin-right: 6px; {} color: {}; user-select: none;",
+                        qt.font(), color
+                    );
+                    qt.apply("RLabel", &object_name, state, base)
+                })
             }
         ) {
             (props.text)
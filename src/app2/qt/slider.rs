@@ -2,6 +2,7 @@ use sycamore::prelude::*;
 use std::rc::Rc;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlInputElement};
+use super::style::{use_qt_style, QtState};
 
 #[derive(Props)]
 pub struct QSliderProps {
@@ -13,6 +14,8 @@ pub struct QSliderProps {
     pub orientation: Orientation,
     #[prop(default = false)]
     pub show_value: bool,
+    #[prop(default)]
+    pub object_name: String,
     pub on_change: Rc<dyn Fn(i32)>,
 }
 
@@ -37,6 +40,7 @@ pub fn Slider(props: QSliderProps) -> View {
     let orientation = props.orientation;
     let show_value = props.show_value;
     let on_change = props.on_change;
+    let object_name = props.object_name;
 
     let handle_input = move |e: Event| {
         let target = e.target().unwrap();
@@ -57,41 +61,63 @@ pub fn Slider(props: QSliderProps) -> View {
         ),
     };
 
+    let qt = use_qt_style();
+    let horizontal = orientation == Orientation::Horizontal;
+    let direction = if horizontal { "to bottom" } else { "to right" };
+    let percent = move || ((value.get() - min) as f64 / (max - min) as f64 * 100.0).clamp(0.0, 100.0);
+
+    let track_style = {
+        let object_name = object_name.clone();
+        move || qt.with(|qt| {
+            let (top, bottom) = &qt.palette.groove;
+            let size = if horizontal { "width: 100%; height: 6px;" } else { "width: 6px; height: 100%;" };
+            let base = format!(
+                "position: absolute; {} background: linear-gradient({}, {}, {}); border: 1px solid {}; border-radius: 3px; box-shadow: inset 0 1px 2px rgba(0,0,0,0.1);",
+                size, direction, top, bottom, qt.palette.border
+            );
+            qt.apply("Slider", &object_name, QtState::Normal, base)
+        })
+    };
+
+    let fill_style = move || qt.with(|qt| {
+        let (top, bottom) = &qt.palette.groove_fill;
+        let size = if horizontal {
+            format!("height: 6px; width: {}%; transition: width 0.05s ease;", percent())
+        } else {
+            format!("width: 6px; bottom: 0; height: {}%; transition: height 0.05s ease;", percent())
+        };
+        format!(
+            "position: absolute; {} background: linear-gradient({}, {}, {}); border-radius: 3px;",
+            size, direction, top, bottom
+        )
+    });
+
+    let thumb_style = move || qt.with(|qt| {
+        let (top, bottom) = &qt.palette.button;
+        let position = if horizontal {
+            format!("left: calc({}% - 8px); transition: left 0.05s ease;", percent())
+        } else {
+            format!("bottom: calc({}% - 8px); transition: bottom 0.05s ease;", percent())
+        };
+        format!(
+            "position: absolute; width: 16px; height: 16px; background: linear-gradient({}, {}, {}); border: 1px solid {}; border-radius: 8px; cursor: pointer; {} box-shadow: 0 1px 3px rgba(0,0,0,0.2); pointer-events: none;",
+            direction, top, bottom, qt.palette.border, position
+        )
+    });
+
+    let value_style = move || qt.with(|qt| format!("{} min-width: 40px; text-align: right;", qt.font()));
+
     view! {
         div(style = wrapper_style) {
             div(style = container_style) {
                 // Track (fundo)
-                div(style = if orientation == Orientation::Horizontal {
-                    "position: absolute; width: 100%; height: 6px; background: linear-gradient(to bottom, #b0b0b0, #c8c8c8); border: 1px solid #8c8c8c; border-radius: 3px; box-shadow: inset 0 1px 2px rgba(0,0,0,0.1);"
-                } else {
-                    "position: absolute; width: 6px; height: 100%; background: linear-gradient(to right, #b0b0b0, #c8c8c8); border: 1px solid #8c8c8c; border-radius: 3px; box-shadow: inset 0 1px 2px rgba(0,0,0,0.1);"
-                })
+                div(style = track_style)
                 
                 // Filled track (parte preenchida)
-                div(style = if orientation == Orientation::Horizontal {
-                    format!(
-                        "position: absolute; height: 6px; background: linear-gradient(to bottom, #5c9fdb, #4a8fd4); border-radius: 3px; width: {}%; transition: width 0.05s ease;",
-                        ((value.get() - min) as f64 / (max - min) as f64 * 100.0).max(0.0).min(100.0)
-                    )
-                } else {
-                    format!(
-                        "position: absolute; width: 6px; background: linear-gradient(to right, #5c9fdb, #4a8fd4); border-radius: 3px; bottom: 0; height: {}%; transition: height 0.05s ease;",
-                        ((value.get() - min) as f64 / (max - min) as f64 * 100.0).max(0.0).min(100.0)
-                    )
-                })
+                div(style = fill_style)
                 
                 // Thumb (círculo deslizante)
-                div(style = if orientation == Orientation::Horizontal {
-                    format!(
-                        "position: absolute; width: 16px; height: 16px; background: linear-gradient(to bottom, #f0f0f0, #d8d8d8); border: 1px solid #8c8c8c; border-radius: 8px; cursor: pointer; left: calc({}% - 8px); box-shadow: 0 1px 3px rgba(0,0,0,0.2); transition: left 0.05s ease; pointer-events: none;",
-                        ((value.get() - min) as f64 / (max - min) as f64 * 100.0).max(0.0).min(100.0)
-                    )
-                } else {
-                    format!(
-                        "position: absolute; width: 16px; height: 16px; background: linear-gradient(to right, #f0f0f0, #d8d8d8); border: 1px solid #8c8c8c; border-radius: 8px; cursor: pointer; bottom: calc({}% - 8px); box-shadow: 0 1px 3px rgba(0,0,0,0.2); transition: bottom 0.05s ease; pointer-events: none;",
-                        ((value.get() - min) as f64 / (max - min) as f64 * 100.0).max(0.0).min(100.0)
-                    )
-                })
+                div(style = thumb_style)
                 
                 // Input range invisível para controle
                 input(
@@ -107,7 +133,7 @@ pub fn Slider(props: QSliderProps) -> View {
             
             (if show_value {
                 view! {
-                    span(style = "font-family: 'Segoe UI', Arial, sans-serif; font-size: 13px; color: #333; min-width: 40px; text-align: right;") {
+                    span(style = value_style) {
                         (value.get().to_string())
                     }
                 }
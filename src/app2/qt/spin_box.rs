@@ -4,6 +4,7 @@ use wasm_bindgen::prelude::*;
 use web_sys::MouseEvent;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlInputElement};
+use super::style::{use_qt_style, QtState};
 
 
 
@@ -24,6 +25,19 @@ pub struct SpinBoxProps {
     #[prop(default)]
     pub disabled: bool,
     
+    // Com `false` o valor fora de [min, max] é repassado para a validação do formulário.
+    #[prop(default = true)]
+    pub clamp: bool,
+    
+    #[prop(default)]
+    pub error: Signal<Option<String>>,
+    
+    #[prop(default)]
+    pub touched: Signal<bool>,
+    
+    #[prop(default)]
+    pub object_name: String,
+    
     pub on_change: Rc<dyn Fn(i32)>,
 }
 
@@ -39,60 +53,9 @@ pub fn SpinBox(props: SpinBoxProps) -> View {
         height: 26px;
     "#.to_string();
 
-    let input_style = r#"
-        box-sizing: border-box;
-        width: 100px;
-        height: 26px;
-        padding: 4px 8px 4px 6px;
-        background: #ffffff;
-        border: 1px solid #8f8f8f;
-        border-radius: 3px;
-        font-family: 'Segoe UI', 'DejaVu Sans', Arial, sans-serif;
-        font-size: 13px;
-        color: #000000;
-        outline: none;
-    "#.to_string();
-
-    let input_hover_style = input_style.clone() + r#"
-        border-color: #5a8dee;
-    "#;
-
-    let input_focus_style = input_style.clone() + r#"
-        border-color: #377af5;
-        box-shadow: inset 0 0 0 1px rgba(55,122,245,0.6);
-    "#;
-
-    let input_disabled_style = input_style.clone() + r#"
-        background: #efefef;
-        color: #7f7f7f;
-        border-color: #bfbfbf;
-    "#;
-
-    let button_style = r#"
-        width: 18px;
-        height: 11px;
-        background: linear-gradient(to bottom, #f6f6f6, #dcdcdc);
-        border: 1px solid #8f8f8f;
-        cursor: pointer;
-        display: flex;
-        align-items: center;
-        justify-content: center;
-        font-size: 8px;
-        user-select: none;
-        padding: 0;
-        margin: 0;
-    "#.to_string();
-
-    let button_hover_style = button_style.clone() + r#"
-        background: linear-gradient(to bottom, #ffffff, #e6e6e6);
-    "#;
-
-    let button_disabled_style = button_style.clone() + r#"
-        background: #efefef;
-        border-color: #bfbfbf;
-        color: #7f7f7f;
-        cursor: default;
-    "#;
+    let qt = use_qt_style();
+    let object_name = props.object_name;
+    let error = props.error;
 
     let on_change = props.on_change.clone();
     let on_change_up = on_change.clone();
@@ -102,14 +65,6 @@ pub fn SpinBox(props: SpinBoxProps) -> View {
     let up_hover = create_signal(false);
     let down_hover = create_signal(false);
 
-    let button_disabled_style_1 = button_disabled_style.clone();
-    let button_disabled_style_2 = button_disabled_style.clone();
-
-    let button_hover_style_1 = button_hover_style.clone();
-    let button_hover_style_2 = button_hover_style.clone();
-
-    let button_style_1 = button_style.clone();
-
     view! {
         div(style = container_style) {
             input(
@@ -120,26 +75,38 @@ pub fn SpinBox(props: SpinBoxProps) -> View {
                 step = props.step.to_string(),
                 disabled = props.disabled,
                 style = move || {
-                    if props.disabled {
-                        input_disabled_style.clone()
+                    let state = if props.disabled {
+                        QtState::Disabled
+                    } else if error.with(|e| e.is_some()) {
+                        QtState::Invalid
                     } else if is_focus.get() {
-                        input_focus_style.clone()
+                        QtState::Focus
                     } else if is_hover.get() {
-                        input_hover_style.clone()
+                        QtState::Hover
                     } else {
-                        input_style.clone()
-                    }
+                        QtState::Normal
+                    };
+                    qt.with(|qt| {
+                        let base = format!(
+                            "width: 100px; height: {}px; padding: 4px 8px 4px 6px; {}",
+                            qt.metrics.control_height, qt.field(state)
+                        );
+                        qt.apply("SpinBox", &object_name, state, base)
+                    })
                 },
                 on:mouseenter = move |_| is_hover.set(true),
                 on:mouseleave = move |_| is_hover.set(false),
                 on:focus = move |_| is_focus.set(true),
-                on:blur = move |_| is_focus.set(false),
+                on:blur = move |_| {
+                    is_focus.set(false);
+                    props.touched.set(true);
+                },
                 on:input = move |e: Event| {
                     if !props.disabled {
                         let input = e.target().unwrap().dyn_into::<HtmlInputElement>().unwrap();
                         if let Ok(val) = input.value().parse::<i32>() {
-                            let clamped = val.clamp(props.min, props.max);
-                            (on_change_input)(clamped);
+                            let val = if props.clamp { val.clamp(props.min, props.max) } else { val };
+                            (on_change_input)(val);
                         }
                     }
                 }
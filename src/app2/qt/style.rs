@@ -0,0 +1,260 @@
+use sycamore::prelude::*;
+
+// --- PALETA / MÉTRICAS / FONTES ---
+
+#[derive(Clone, PartialEq)]
+pub struct QtPalette {
+    pub base: String,
+    pub text: String,
+    pub border: String,
+    pub hover: String,
+    pub highlight: String,
+    pub highlight_dark: String,
+    pub highlight_glow: String,
+    pub disabled_base: String,
+    pub disabled_text: String,
+    pub disabled_border: String,
+    pub danger: String,
+    pub danger_glow: String,
+    // Gradientes (topo, base) dos botões
+    pub button: (String, String),
+    pub button_hover: (String, String),
+    pub button_pressed: (String, String),
+    pub groove: (String, String),
+    pub groove_fill: (String, String),
+}
+
+impl Default for QtPalette {
+    fn default() -> Self {
+        Self {
+            base: "#ffffff".to_string(),
+            text: "#000000".to_string(),
+            border: "#8f8f8f".to_string(),
+            hover: "#5a8dee".to_string(),
+            highlight: "#377af5".to_string(),
+            highlight_dark: "#2a5fd4".to_string(),
+            highlight_glow: "rgba(55,122,245,0.6)".to_string(),
+            disabled_base: "#efefef".to_string(),
+            disabled_text: "#7f7f7f".to_string(),
+            disabled_border: "#bfbfbf".to_string(),
+            danger: "#d93025".to_string(),
+            danger_glow: "rgba(217,48,37,0.5)".to_string(),
+            button: ("#f6f6f6".to_string(), "#dcdcdc".to_string()),
+            button_hover: ("#ffffff".to_string(), "#e6e6e6".to_string()),
+            button_pressed: ("#d6d6d6".to_string(), "#f0f0f0".to_string()),
+            groove: ("#b0b0b0".to_string(), "#c8c8c8".to_string()),
+            groove_fill: ("#5c9fdb".to_string(), "#4a8fd4".to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct QtMetrics {
+    pub control_height: u32,
+    pub radius: u32,
+    pub font_size: u32,
+    pub small_font_size: u32,
+}
+
+impl Default for QtMetrics {
+    fn default() -> Self {
+        Self { control_height: 26, radius: 3, font_size: 13, small_font_size: 11 }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct QtFonts {
+    pub family: String,
+    pub mono: String,
+}
+
+impl Default for QtFonts {
+    fn default() -> Self {
+        Self {
+            family: "'Segoe UI', 'DejaVu Sans', Arial, sans-serif".to_string(),
+            mono: "'Consolas', 'DejaVu Sans Mono', monospace".to_string(),
+        }
+    }
+}
+
+// --- FOLHA DE ESTILO (QSS) ---
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum QtState { Normal, Hover, Focus, Pressed, Checked, Disabled, Invalid }
+
+impl QtState {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "hover" => Some(QtState::Hover),
+            "focus" => Some(QtState::Focus),
+            "pressed" => Some(QtState::Pressed),
+            "checked" => Some(QtState::Checked),
+            "disabled" => Some(QtState::Disabled),
+            "invalid" => Some(QtState::Invalid),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct QssRule {
+    // `None` casa com qualquer widget (`*` ou só `#nome`)
+    widget: Option<String>,
+    object_name: Option<String>,
+    state: Option<QtState>,
+    declarations: String,
+}
+
+impl QssRule {
+    fn matches(&self, widget: &str, object_name: &str, state: QtState) -> bool {
+        self.widget.as_deref().is_none_or(|w| w == widget)
+            && self.object_name.as_deref().is_none_or(|n| n == object_name)
+            && self.state.is_none_or(|s| s == state)
+    }
+
+    // Como no CSS: #nome pesa mais que o tipo; o estado desempata.
+    fn specificity(&self) -> u8 {
+        self.object_name.is_some() as u8 * 4
+            + self.widget.is_some() as u8 * 2
+            + self.state.is_some() as u8
+    }
+}
+
+// Subconjunto da sintaxe QSS:
+//
+//     LineEdit { border-radius: 0; }
+//     PushButton:hover, SpinBox:focus { border-color: orange; }
+//     PushButton#salvar:pressed { background: #1e8e3e; }
+//
+// As declarações são CSS comum, aplicadas depois do estilo padrão do widget.
+#[derive(Clone, PartialEq, Default)]
+pub struct StyleSheet {
+    rules: Vec<QssRule>,
+}
+
+impl StyleSheet {
+    pub fn parse(source: &str) -> Self {
+        let mut source = source.to_string();
+        while let Some(start) = source.find("/*") {
+            let end = source[start..].find("*/").map(|e| start + e + 2).unwrap_or(source.len());
+            source.replace_range(start..end, "");
+        }
+
+        let mut rules = Vec::new();
+        for block in source.split('}') {
+            let Some((selectors, declarations)) = block.split_once('{') else { continue };
+            let declarations = declarations.trim().to_string();
+            for selector in selectors.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                let (selector, state) = match selector.split_once(':') {
+                    Some((sel, state)) => match QtState::parse(state.trim()) {
+                        Some(state) => (sel, Some(state)),
+                        None => continue,
+                    },
+                    None => (selector, None),
+                };
+                let (widget, object_name) = match selector.split_once('#') {
+                    Some((w, n)) => (w.trim(), Some(n.trim().to_string())),
+                    None => (selector.trim(), None),
+                };
+                let widget = (!widget.is_empty() && widget != "*").then(|| widget.to_string());
+                rules.push(QssRule { widget, object_name, state, declarations: declarations.clone() });
+            }
+        }
+        Self { rules }
+    }
+
+    fn declarations(&self, widget: &str, object_name: &str, state: QtState) -> String {
+        let mut matching: Vec<&QssRule> = self.rules.iter()
+            .filter(|r| r.matches(widget, object_name, state))
+            .collect();
+        // sort estável: mesma especificidade mantém a ordem do código-fonte
+        matching.sort_by_key(|r| r.specificity());
+        matching.iter().map(|r| r.declarations.as_str()).collect::<Vec<_>>().join(" ")
+    }
+}
+
+// --- ESTILO ---
+
+#[derive(Clone, PartialEq, Default)]
+pub struct QtStyle {
+    pub palette: QtPalette,
+    pub metrics: QtMetrics,
+    pub fonts: QtFonts,
+    pub sheet: StyleSheet,
+}
+
+impl QtStyle {
+    pub fn with_sheet(mut self, source: &str) -> Self {
+        self.sheet = StyleSheet::parse(source);
+        self
+    }
+
+    // Estilo padrão do widget seguido das regras da folha que casam com ele.
+    pub fn apply(&self, widget: &str, object_name: &str, state: QtState, base: String) -> String {
+        let overrides = self.sheet.declarations(widget, object_name, state);
+        if overrides.is_empty() { base } else { format!("{} {}", base, overrides) }
+    }
+
+    pub fn font(&self) -> String {
+        format!(
+            "font-family: {}; font-size: {}px; color: {};",
+            self.fonts.family, self.metrics.font_size, self.palette.text
+        )
+    }
+
+    // Caixa de edição compartilhada por LineEdit, SpinBox, DoubleSpinBox e TextEdit.
+    pub fn field(&self, state: QtState) -> String {
+        let p = &self.palette;
+        let base = format!(
+            "box-sizing: border-box; background: {}; border: 1px solid {}; border-radius: {}px; {} outline: none;",
+            p.base, p.border, self.metrics.radius, self.font()
+        );
+        let extra = match state {
+            QtState::Hover => format!("border-color: {};", p.hover),
+            QtState::Focus => format!("border-color: {}; box-shadow: inset 0 0 0 1px {};", p.highlight, p.highlight_glow),
+            QtState::Invalid => format!("border-color: {}; box-shadow: inset 0 0 0 1px {};", p.danger, p.danger_glow),
+            QtState::Disabled => format!("background: {}; color: {}; border-color: {};", p.disabled_base, p.disabled_text, p.disabled_border),
+            QtState::Normal | QtState::Pressed | QtState::Checked => String::new(),
+        };
+        base + &extra
+    }
+
+    pub fn button(&self, state: QtState) -> String {
+        let p = &self.palette;
+        let gradient = |(top, bottom): &(String, String)| format!("linear-gradient(to bottom, {}, {})", top, bottom);
+        let base = format!(
+            "background: {}; border: 1px solid {}; {} box-shadow: inset 0 1px 0 rgba(255,255,255,0.8); cursor: pointer; outline: none; user-select: none;",
+            gradient(&p.button), p.border, self.font()
+        );
+        let extra = match state {
+            QtState::Hover | QtState::Focus => format!("background: {}; border-color: {};", gradient(&p.button_hover), p.hover),
+            QtState::Pressed | QtState::Checked => format!(
+                "background: {}; border-color: {}; box-shadow: inset 0 2px 4px rgba(0,0,0,0.15);",
+                gradient(&p.button_pressed), p.hover
+            ),
+            QtState::Disabled => format!(
+                "background: {}; border-color: {}; color: {}; cursor: default; box-shadow: none;",
+                p.disabled_base, p.disabled_border, p.disabled_text
+            ),
+            QtState::Normal | QtState::Invalid => String::new(),
+        };
+        base + &extra
+    }
+}
+
+pub fn use_qt_style() -> Signal<QtStyle> {
+    try_use_context::<Signal<QtStyle>>().unwrap_or_else(|| create_signal(QtStyle::default()))
+}
+
+#[derive(Props)]
+pub struct QtStyleProviderProps {
+    pub children: Children,
+    // Trocar o valor do sinal reestiliza todos os widgets abaixo
+    pub style: Signal<QtStyle>,
+}
+
+#[component]
+pub fn QtStyleProvider(props: QtStyleProviderProps) -> View {
+    provide_context(props.style);
+    props.children.call()
+}
@@ -2,6 +2,7 @@ use sycamore::prelude::*;
 use std::rc::Rc;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlTextAreaElement, FocusEvent, MouseEvent};
+use super::style::{use_qt_style, QtState};
 
 #[derive(Props)]
 pub struct TextEditProps {
@@ -14,6 +15,8 @@ pub struct TextEditProps {
     pub height: i32,
     #[prop(default = false)]
     pub line_wrap: bool,
+    #[prop(default)]
+    pub object_name: String,
     pub on_change: Rc<dyn Fn(String)>,
 }
 
@@ -44,32 +47,49 @@ pub fn TextEdit(props: TextEditProps) -> View {
         is_focused.set(false);
     };
 
+    let qt = use_qt_style();
+    let object_name = props.object_name;
+
     let base_style = create_memo(move || {
-        format!(
-            "width: 100%; \
-             height: {}px; \
-             padding: 6px 8px; \
-             font-family: 'Segoe UI', 'Consolas', monospace; \
-             font-size: 13px; \
-             color: #000; \
-             background: {}; \
-             border: 1px solid {}; \
-             border-radius: 2px; \
-             resize: vertical; \
-             outline: none; \
-             box-shadow: {}; \
-             transition: border-color 0.15s ease, box-shadow 0.15s ease; \
-             {}",
-            height,
-            if readonly { "#f0f0f0" } else { "#ffffff" },
-            if is_focused.get() { "#5c9fdb" } else { "#a0a0a0" },
-            if is_focused.get() { 
-                "inset 0 1px 2px rgba(0,0,0,0.05), 0 0 0 2px rgba(92,159,219,0.2)" 
-            } else { 
-                "inset 0 1px 2px rgba(0,0,0,0.05)" 
-            },
-            if line_wrap { "white-space: pre-wrap; word-wrap: break-word;" } else { "white-space: pre; overflow-x: auto;" }
-        )
+        let state = if readonly {
+            QtState::Disabled
+        } else if is_focused.get() {
+            QtState::Focus
+        } else {
+            QtState::Normal
+        };
+        qt.with(|qt| {
+            let p = &qt.palette;
+            let base = format!(
+                "width: 100%; \
+                 height: {}px; \
+                 padding: 6px 8px; \
+                 font-family: {}; \
+                 font-size: {}px; \
+                 color: {}; \
+                 background: {}; \
+                 border: 1px solid {}; \
+                 border-radius: 2px; \
+                 resize: vertical; \
+                 outline: none; \
+                 box-shadow: {}; \
+                 transition: border-color 0.15s ease, box-shadow 0.15s ease; \
+                 {}",
+                height,
+                qt.fonts.family,
+                qt.metrics.font_size,
+                p.text,
+                if readonly { &p.disabled_base } else { &p.base },
+                if is_focused.get() { &p.highlight } else { &p.border },
+                if is_focused.get() {
+                    format!("inset 0 1px 2px rgba(0,0,0,0.05), 0 0 0 2px {}", p.highlight_glow)
+                } else {
+                    "inset 0 1px 2px rgba(0,0,0,0.05)".to_string()
+                },
+                if line_wrap { "white-space: pre-wrap; word-wrap: break-word;" } else { "white-space: pre; overflow-x: auto;" }
+            );
+            qt.apply("TextEdit", &object_name, state, base)
+        })
     });
 
     view! {
@@ -81,7 +101,7 @@ pub fn TextEdit(props: TextEditProps) -> View {
                 on:input = handle_input,
                 on:focus = handle_focus,
                 on:blur = handle_blur,
-                style = base_style.get_clone()
+                style = move || base_style.get_clone()
             )
         }
     }
@@ -101,6 +121,8 @@ pub struct QTextEditRichProps {
     pub line_wrap: bool,
     #[prop(default = true)]
     pub show_toolbar: bool,
+    #[prop(default)]
+    pub object_name: String,
     pub on_change: Rc<dyn Fn(String)>,
 }
 
@@ -156,61 +178,85 @@ pub fn QTextEditRich(props: QTextEditRichProps) -> View {
         }
     };
 
+    let qt = use_qt_style();
+    let object_name = props.object_name;
+
     let base_style = create_memo(move || {
+        let state = if readonly {
+            QtState::Disabled
+        } else if is_focused.get() {
+            QtState::Focus
+        } else {
+            QtState::Normal
+        };
+        qt.with(|qt| {
+            let p = &qt.palette;
+            let base = format!(
+                "width: 100%; \
+                 height: {}px; \
+                 padding: 8px; \
+                 {} \
+                 background: {}; \
+                 border: 1px solid {}; \
+                 border-top: {}; \
+                 border-radius: 0 0 2px 2px; \
+                 resize: vertical; \
+                 outline: none; \
+                 box-shadow: inset 0 1px 2px rgba(0,0,0,0.05); \
+                 transition: border-color 0.15s ease; \
+                 {}",
+                height,
+                qt.font(),
+                if readonly { &p.disabled_base } else { &p.base },
+                if is_focused.get() { &p.highlight } else { &p.border },
+                if show_toolbar { "none".to_string() } else { format!("1px solid {}", p.border) },
+                if line_wrap { "white-space: pre-wrap; word-wrap: break-word;" } else { "white-space: pre; overflow-x: auto;" }
+            );
+            qt.apply("QTextEditRich", &object_name, state, base)
+        })
+    });
+
+    let frame_style = move || qt.with(|qt| format!(
+        "position: relative; width: 100%; border: 1px solid {}; border-radius: 2px; background: {};",
+        if is_focused.get() { &qt.palette.highlight } else { &qt.palette.border },
+        qt.palette.base
+    ));
+
+    let toolbar_style = move || qt.with(|qt| {
+        let (top, bottom) = &qt.palette.button;
         format!(
-            "width: 100%; \
-             height: {}px; \
-             padding: 8px; \
-             font-family: 'Segoe UI', Arial, sans-serif; \
-             font-size: 13px; \
-             color: #000; \
-             background: {}; \
-             border: 1px solid {}; \
-             border-top: {}; \
-             border-radius: 0 0 2px 2px; \
-             resize: vertical; \
-             outline: none; \
-             box-shadow: {}; \
-             transition: border-color 0.15s ease; \
-             {}",
-            height,
-            if readonly { "#f0f0f0" } else { "#ffffff" },
-            if is_focused.get() { "#5c9fdb" } else { "#a0a0a0" },
-            if show_toolbar { "none" } else { "1px solid #a0a0a0" },
-            if is_focused.get() { 
-                "inset 0 1px 2px rgba(0,0,0,0.05)" 
-            } else { 
-                "inset 0 1px 2px rgba(0,0,0,0.05)" 
-            },
-            if line_wrap { "white-space: pre-wrap; word-wrap: break-word;" } else { "white-space: pre; overflow-x: auto;" }
+            "display: flex; gap: 2px; padding: 4px; background: linear-gradient(to bottom, {}, {}); border-bottom: 1px solid {};",
+            top, bottom, qt.palette.border
         )
     });
 
+    let tool_button_style = move |extra: &str| qt.with(|qt| format!(
+        "padding: 4px 8px; border-radius: 2px; min-width: 28px; height: 24px; display: flex; align-items: center; justify-content: center; {} font-size: 12px; {}",
+        qt.button(QtState::Normal), extra
+    ));
+
     view! {
-        div(style = format!(
-            "position: relative; width: 100%; border: 1px solid {}; border-radius: 2px; background: #fff;",
-            if is_focused.get() { "#5c9fdb" } else { "#a0a0a0" }
-        )) {
+        div(style = frame_style) {
             (if show_toolbar {
                 view! {
-                    div(style = "display: flex; gap: 2px; padding: 4px; background: linear-gradient(to bottom, #f5f5f5, #e8e8e8); border-bottom: 1px solid #c0c0c0;") {
+                    div(style = toolbar_style) {
                         button(
                             on:click = insert_bold,
-                            style = "padding: 4px 8px; background: linear-gradient(to bottom, #ffffff, #e0e0e0); border: 1px solid #a0a0a0; border-radius: 2px; cursor: pointer; font-weight: bold; font-size: 12px; min-width: 28px; height: 24px; display: flex; align-items: center; justify-content: center;",
+                            style = tool_button_style("font-weight: bold;"),
                             title = "Negrito"
                         ) { "B" }
                         
                         button(
                             on:click = insert_italic,
-                            style = "padding: 4px 8px; background: linear-gradient(to bottom, #ffffff, #e0e0e0); border: 1px solid #a0a0a0; border-radius: 2px; cursor: pointer; font-style: italic; font-size: 12px; min-width: 28px; height: 24px; display: flex; align-items: center; justify-content: center;",
+                            style = tool_button_style("font-style: italic;"),
                             title = "Itálico"
                         ) { "I" }
                         
-                        div(style = "width: 1px; background: #c0c0c0; margin: 2px 4px;")
+                        div(style = move || qt.with(|qt| format!("width: 1px; background: {}; margin: 2px 4px;", qt.palette.border)))
                         
                         button(
                             on:click = clear_text,
-                            style = "padding: 4px 8px; background: linear-gradient(to bottom, #ffffff, #e0e0e0); border: 1px solid #a0a0a0; border-radius: 2px; cursor: pointer; font-size: 11px; color: #d00;",
+                            style = move || qt.with(|qt| format!("{} color: {};", tool_button_style(""), qt.palette.danger)),
                             title = "Limpar"
                         ) { "Limpar" }
                     }
@@ -226,7 +272,7 @@ pub fn QTextEditRich(props: QTextEditRichProps) -> View {
                 on:input = handle_input,
                 on:focus = handle_focus,
                 on:blur = handle_blur,
-                style = base_style.get_clone()
+                style = move || base_style.get_clone()
             )
         }
     }
@@ -0,0 +1,300 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::rc::Rc;
+use futures::future::{join_all, FutureExt, LocalBoxFuture};
+use sycamore::prelude::*;
+
+// --- VALIDADORES ---
+
+pub type ValidationResult = Result<(), String>;
+
+pub struct Validator<T>(Rc<dyn Fn(&T) -> ValidationResult>);
+
+impl<T> Clone for Validator<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+pub struct AsyncValidator<T>(Rc<dyn Fn(T) -> LocalBoxFuture<'static, ValidationResult>>);
+
+impl<T> Clone for AsyncValidator<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+pub fn custom<T, F>(f: F) -> Validator<T>
+where F: Fn(&T) -> ValidationResult + 'static {
+    Validator(Rc::new(f))
+}
+
+// Ex.: verificar no servidor se o e-mail já está em uso via `HttpClient`.
+pub fn custom_async<T, F, Fut>(f: F) -> AsyncValidator<T>
+where
+    F: Fn(T) -> Fut + 'static,
+    Fut: Future<Output = ValidationResult> + 'static,
+{
+    AsyncValidator(Rc::new(move |value| f(value).boxed_local()))
+}
+
+pub fn required(message: &'static str) -> Validator<String> {
+    custom(move |v: &String| if v.trim().is_empty() { Err(message.to_string()) } else { Ok(()) })
+}
+
+pub fn min_length(min: usize, message: &'static str) -> Validator<String> {
+    custom(move |v: &String| if v.chars().count() < min { Err(message.to_string()) } else { Ok(()) })
+}
+
+pub fn max_length(max: usize, message: &'static str) -> Validator<String> {
+    custom(move |v: &String| if v.chars().count() > max { Err(message.to_string()) } else { Ok(()) })
+}
+
+// Usa o RegExp do navegador; campos vazios passam (combine com `required`).
+pub fn pattern(regex: &'static str, message: &'static str) -> Validator<String> {
+    let re = js_sys::RegExp::new(regex, "u");
+    custom(move |v: &String| {
+        if v.is_empty() || re.test(v) { Ok(()) } else { Err(message.to_string()) }
+    })
+}
+
+pub fn email(message: &'static str) -> Validator<String> {
+    pattern(r"^[^\s@]+@[^\s@]+\.[^\s@]+$", message)
+}
+
+pub fn range<T>(min: T, max: T) -> Validator<T>
+where T: PartialOrd + Display + Copy + 'static {
+    custom(move |v: &T| {
+        if *v < min || *v > max {
+            Err(format!("Valor deve estar entre {} e {}", min, max))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+// --- CAMPO ---
+
+pub struct Field<T: 'static> {
+    pub value: Signal<T>,
+    pub error: Signal<Option<String>>,
+    pub touched: Signal<bool>,
+    pub validating: Signal<bool>,
+    initial: Signal<T>,
+    validators: Signal<Vec<Validator<T>>>,
+    async_validators: Signal<Vec<AsyncValidator<T>>>,
+    // Descarta respostas de validações assíncronas que ficaram obsoletas
+    generation: Signal<u32>,
+}
+
+impl<T> Clone for Field<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Field<T> {}
+
+impl<T: Clone + PartialEq + 'static> Field<T> {
+    pub fn new(initial: T) -> Self {
+        Self::from_signal(create_signal(initial))
+    }
+
+    // Reaproveita um sinal existente (ex.: campos do `State`).
+    pub fn from_signal(value: Signal<T>) -> Self {
+        let field = Self {
+            value,
+            error: create_signal(None),
+            touched: create_signal(false),
+            validating: create_signal(false),
+            initial: create_signal(value.get_clone_untracked()),
+            validators: create_signal(Vec::new()),
+            async_validators: create_signal(Vec::new()),
+            generation: create_signal(0),
+        };
+
+        // Depois do primeiro blur o erro acompanha a digitação
+        create_effect(move || {
+            value.track();
+            if field.touched.get() {
+                field.check();
+            }
+        });
+
+        field
+    }
+
+    pub fn validate(self, validator: Validator<T>) -> Self {
+        self.validators.update(|v| v.push(validator));
+        self
+    }
+
+    pub fn validate_async(self, validator: AsyncValidator<T>) -> Self {
+        self.async_validators.update(|v| v.push(validator));
+        self
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.value.with(|v| self.initial.with(|i| v != i))
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.error.with(|e| e.is_none())
+    }
+
+    pub fn touch(&self) {
+        self.touched.set(true);
+    }
+
+    // Roda só os validadores síncronos; devolve se o campo está válido.
+    pub fn check(&self) -> bool {
+        let value = self.value.get_clone_untracked();
+        let result = self.validators.with_untracked(|validators| {
+            validators.iter().try_for_each(|v| (v.0)(&value))
+        });
+        let valid = result.is_ok();
+        self.error.set(result.err());
+        valid
+    }
+
+    pub async fn check_async(&self) -> bool {
+        if !self.check() {
+            return false;
+        }
+        let validators = self.async_validators.get_clone_untracked();
+        if validators.is_empty() {
+            return true;
+        }
+
+        let generation = self.generation.get_untracked() + 1;
+        self.generation.set(generation);
+        self.validating.set(true);
+
+        let value = self.value.get_clone_untracked();
+        let results = join_all(validators.iter().map(|v| (v.0)(value.clone()))).await;
+
+        if !self.generation.is_alive() || self.generation.get_untracked() != generation {
+            return false;
+        }
+        self.validating.set(false);
+        let result = results.into_iter().collect::<Result<(), String>>();
+        let valid = result.is_ok();
+        self.error.set(result.err());
+        valid
+    }
+
+    pub fn reset(&self) {
+        self.value.set(self.initial.get_clone_untracked());
+        self.error.set(None);
+        self.touched.set(false);
+    }
+
+    // Marca o valor atual como o novo "limpo" (ex.: após salvar).
+    pub fn commit(&self) {
+        self.initial.set(self.value.get_clone_untracked());
+    }
+}
+
+// --- FORMULÁRIO ---
+
+trait FormField {
+    fn touch(&self);
+    fn validate(&self) -> LocalBoxFuture<'static, bool>;
+    fn is_dirty(&self) -> bool;
+    fn is_valid(&self) -> bool;
+    fn reset(&self);
+    fn commit(&self);
+}
+
+impl<T: Clone + PartialEq + 'static> FormField for Field<T> {
+    fn touch(&self) {
+        Field::touch(self)
+    }
+
+    fn validate(&self) -> LocalBoxFuture<'static, bool> {
+        let field = *self;
+        async move { field.check_async().await }.boxed_local()
+    }
+
+    fn is_dirty(&self) -> bool {
+        Field::is_dirty(self)
+    }
+
+    fn is_valid(&self) -> bool {
+        Field::is_valid(self)
+    }
+
+    fn reset(&self) {
+        Field::reset(self)
+    }
+
+    fn commit(&self) {
+        Field::commit(self)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Form {
+    fields: Signal<Vec<Rc<dyn FormField>>>,
+    pub submitting: Signal<bool>,
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Form {
+    pub fn new() -> Self {
+        Self {
+            fields: create_signal(Vec::new()),
+            submitting: create_signal(false),
+        }
+    }
+
+    pub fn field<T: Clone + PartialEq + 'static>(&self, initial: T) -> Field<T> {
+        self.register(Field::new(initial))
+    }
+
+    pub fn register<T: Clone + PartialEq + 'static>(&self, field: Field<T>) -> Field<T> {
+        self.fields.update(|f| f.push(Rc::new(field)));
+        field
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.fields.with(|f| f.iter().all(|f| f.is_valid()))
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.fields.with(|f| f.iter().any(|f| f.is_dirty()))
+    }
+
+    pub fn reset(&self) {
+        self.fields.with_untracked(|f| f.iter().for_each(|f| f.reset()));
+    }
+
+    // Ex.: depois de carregar valores salvos, para não contarem como alteração.
+    pub fn commit(&self) {
+        self.fields.with_untracked(|f| f.iter().for_each(|f| f.commit()));
+    }
+
+    // Valida todos os campos e só chama `on_valid` se nenhum tiver erro.
+    pub async fn submit<F, Fut>(&self, on_valid: F) -> bool
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let fields = self.fields.get_clone_untracked();
+        fields.iter().for_each(|f| f.touch());
+
+        self.submitting.set(true);
+        let valid = join_all(fields.iter().map(|f| f.validate())).await.into_iter().all(|ok| ok);
+        if valid {
+            on_valid().await;
+            fields.iter().for_each(|f| f.commit());
+        }
+        self.submitting.set(false);
+        valid
+    }
+}
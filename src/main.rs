@@ -1,6 +1,11 @@
 #[allow(unused)]
-
+mod app;
+#[allow(unused)]
 mod app2;
+#[allow(unused)]
+mod form;
+#[allow(unused)]
+mod tauri_bridge;
 
 use app2::App;
 
@@ -0,0 +1,89 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    // `catch` transforma a Promise rejeitada (o `Err` do comando) em `Err(JsValue)`
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "core"], js_name = invoke)]
+    async fn raw_invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+// --- ERROS ---
+
+#[derive(Debug, Clone)]
+pub enum InvokeError {
+    // Rodando no navegador (`trunk serve`), sem o shell do Tauri
+    Unavailable,
+    Args(String),
+    // `Err` devolvido pelo comando no backend
+    Backend(String),
+    Response(String),
+}
+
+impl std::fmt::Display for InvokeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvokeError::Unavailable => write!(f, "Tauri indisponível fora do app desktop"),
+            InvokeError::Args(msg) => write!(f, "Argumentos inválidos: {}", msg),
+            InvokeError::Backend(msg) => write!(f, "{}", msg),
+            InvokeError::Response(msg) => write!(f, "Resposta inválida: {}", msg),
+        }
+    }
+}
+
+pub type InvokeResult<T> = Result<T, InvokeError>;
+
+// --- INVOKE ---
+
+pub fn is_tauri() -> bool {
+    web_sys::window()
+        .map(|w| js_sys::Reflect::has(&w, &JsValue::from_str("__TAURI__")).unwrap_or(false))
+        .unwrap_or(false)
+}
+
+// Erros do backend chegam como string (`Result<_, String>`) ou como objeto serializado.
+fn backend_error(err: JsValue) -> InvokeError {
+    let message = err.as_string()
+        .or_else(|| js_sys::JSON::stringify(&err).ok().and_then(|s| s.as_string()))
+        .unwrap_or_else(|| format!("{:?}", err));
+    InvokeError::Backend(message)
+}
+
+// Os nomes dos campos de `args` seguem a convenção do Tauri (camelCase no JS).
+// Mapas viram objetos JS: um `Map` chegaria vazio (`{}`) no IPC.
+pub async fn invoke<A, R>(cmd: &str, args: &A) -> InvokeResult<R>
+where
+    A: Serialize + ?Sized,
+    R: DeserializeOwned,
+{
+    if !is_tauri() {
+        return Err(InvokeError::Unavailable);
+    }
+    let args = args
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| InvokeError::Args(e.to_string()))?;
+    let value = raw_invoke(cmd, args).await.map_err(backend_error)?;
+    serde_wasm_bindgen::from_value(value).map_err(|e| InvokeError::Response(e.to_string()))
+}
+
+// Para comandos que leem `tauri::ipc::Request` e respondem `tauri::ipc::Response`:
+// os bytes vão como `Uint8Array` e voltam num `ArrayBuffer`, sem passar por JSON.
+pub async fn invoke_bytes(cmd: &str, payload: &[u8]) -> InvokeResult<Vec<u8>> {
+    if !is_tauri() {
+        return Err(InvokeError::Unavailable);
+    }
+    let value = raw_invoke(cmd, js_sys::Uint8Array::from(payload).into()).await.map_err(backend_error)?;
+    if value.is_instance_of::<js_sys::ArrayBuffer>() {
+        return Ok(js_sys::Uint8Array::new(&value).to_vec());
+    }
+    serde_wasm_bindgen::from_value(value).map_err(|e| InvokeError::Response(e.to_string()))
+}
+
+// Struct vazia vira `{}`; `()` iria como `null`, que o Tauri não aceita como args.
+#[derive(Serialize)]
+struct NoArgs {}
+
+pub async fn invoke_no_args<R: DeserializeOwned>(cmd: &str) -> InvokeResult<R> {
+    invoke(cmd, &NoArgs {}).await
+}